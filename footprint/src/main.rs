@@ -0,0 +1,119 @@
+//! A representative device used to track the binary size of `tosca-stack`.
+//!
+//! It builds the description of a light, serializes it and parses the route
+//! parameters received by the device, such as `brightness=7.5` and
+//! `save-energy=true`.
+//!
+//! It is built as a bare-metal binary for an embedded target, so its size
+//! only includes `tosca-stack`, its dependencies and a minimal runtime. The
+//! binary size is checked against a recorded budget by
+//! `scripts/binary_size.sh`.
+
+#![no_std]
+#![no_main]
+
+extern crate alloc;
+
+use core::alloc::{GlobalAlloc, Layout};
+use core::cell::UnsafeCell;
+use core::hint::black_box;
+use core::panic::PanicInfo;
+
+use tosca_stack::device::{DeviceData, DeviceEnvironment, DeviceKind};
+use tosca_stack::hazards::{Hazard, Hazards};
+use tosca_stack::parameters::Parameters;
+use tosca_stack::route::{Route, RouteConfigsBuilder};
+
+// Size of the heap used to serialize the device description.
+const HEAP_SIZE: usize = 4096;
+
+// A bump allocator which never frees memory, enough for a single
+// serialization.
+struct BumpAllocator {
+    heap: UnsafeCell<[u8; HEAP_SIZE]>,
+    next: UnsafeCell<usize>,
+}
+
+// The device runs a single thread.
+unsafe impl Sync for BumpAllocator {}
+
+unsafe impl GlobalAlloc for BumpAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let heap = self.heap.get().cast::<u8>();
+        // SAFETY: the device runs a single thread, so the allocator is never
+        // accessed concurrently.
+        let next = unsafe { &mut *self.next.get() };
+        let offset = heap.wrapping_add(*next).align_offset(layout.align());
+        let start = *next + offset;
+        if start + layout.size() > HEAP_SIZE {
+            return core::ptr::null_mut();
+        }
+        *next = start + layout.size();
+        heap.wrapping_add(start)
+    }
+
+    unsafe fn dealloc(&self, _ptr: *mut u8, _layout: Layout) {}
+}
+
+#[global_allocator]
+static ALLOCATOR: BumpAllocator = BumpAllocator {
+    heap: UnsafeCell::new([0; HEAP_SIZE]),
+    next: UnsafeCell::new(0),
+};
+
+#[panic_handler]
+fn panic(_info: &PanicInfo) -> ! {
+    loop {
+        core::hint::spin_loop();
+    }
+}
+
+fn parameters() -> Parameters<4> {
+    Parameters::new()
+        .rangef64("brightness", (0., 20., 0.1))
+        .bool("save-energy", false)
+}
+
+#[unsafe(no_mangle)]
+extern "C" fn _start() -> ! {
+    let route_configs = RouteConfigsBuilder::<4, 4, 4>::new()
+        .route(
+            Route::put("/on")
+                .description("Turns the light on")
+                .with_hazards(
+                    Hazards::<4>::new()
+                        .insert(Hazard::FireHazard)
+                        .insert(Hazard::ElectricEnergyConsumption),
+                )
+                .with_parameters(parameters()),
+        )
+        .unwrap()
+        .route(
+            Route::put("/off")
+                .description("Turns the light off")
+                .with_hazards(Hazards::new())
+                .with_parameters(Parameters::new()),
+        )
+        .unwrap()
+        .build();
+
+    let device = DeviceData::new(
+        DeviceKind::Light,
+        DeviceEnvironment::Esp32,
+        "/light",
+        route_configs,
+    );
+
+    // The serialized description would be sent to a controller.
+    black_box(serde_json::to_vec(&device).unwrap());
+
+    // The parameters would be received from a controller.
+    let parameters = parameters();
+    for (name, text) in black_box([("brightness", "7.5"), ("save-energy", "true")]) {
+        let _ = black_box(parameters.parse(name, text));
+    }
+
+    loop {
+        core::hint::spin_loop();
+    }
+}
@@ -0,0 +1,42 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+use tosca_stack::parameters::{ParameterValue, Parameters, ToscaParameters};
+
+#[derive(ToscaParameters)]
+struct Light {
+    on: bool,
+    mode: u8,
+    #[tosca(range(0, 100, 1), default = 50)]
+    brightness: u64,
+    #[tosca(range(-10., 40., 0.5))]
+    temperature: f64,
+    scale: f32,
+}
+
+// Parses a query-like `name=value&name=value` input.
+fuzz_target!(|input: &str| {
+    let pairs = input.split('&').filter_map(|pair| pair.split_once('='));
+
+    if let Ok(light) = Light::parse(pairs) {
+        assert!(light.brightness <= 100);
+        assert!((-10. ..=40.).contains(&light.temperature));
+        let _ = (light.on, light.mode, light.scale);
+    }
+
+    let parameters = Parameters::<8>::new()
+        .u16("u16", 0)
+        .u32("u32", 0)
+        .f32("f32", 0.)
+        .rangeu64("rangeu64", (10, 20, 1))
+        .rangef64("rangef64", (-1., 1., 0.1));
+
+    for name in ["u16", "u32", "f32", "rangeu64", "rangef64"] {
+        match parameters.parse(name, input) {
+            Ok(ParameterValue::U64(value)) => assert!((10..=20).contains(&value)),
+            Ok(ParameterValue::F64(value)) => assert!((-1. ..=1.).contains(&value)),
+            _ => {}
+        }
+    }
+});
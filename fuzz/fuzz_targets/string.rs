@@ -0,0 +1,36 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+use tosca_stack::string::String;
+
+const N: usize = 16;
+
+fuzz_target!(|input: (&str, &str, char)| {
+    let (text, other, c) = input;
+
+    let Ok(mut string) = String::<N>::new(text) else {
+        assert!(text.len() > N);
+        assert!(String::<N>::infallible(text).is_empty());
+        return;
+    };
+    assert_eq!(string.as_str(), text);
+
+    match string.push(other) {
+        Ok(()) => {
+            assert!(text.len() + other.len() <= N);
+            assert_eq!(string.as_str().strip_prefix(text), Some(other));
+        }
+        Err(_) => {
+            assert!(text.len() + other.len() > N);
+            assert_eq!(string.as_str(), text);
+        }
+    }
+
+    let previous = string.clone();
+    match string.push_char(c) {
+        Ok(()) => assert!(string.as_str().ends_with(c)),
+        Err(_) => assert_eq!(string, previous),
+    }
+    assert!(string.as_str().len() <= N);
+});
@@ -0,0 +1,32 @@
+#!/bin/sh
+#
+# Checks the binary size of the `footprint` crate against a recorded budget.
+#
+# The `footprint` crate is a bare-metal `no_std` binary built with the
+# settings used by constrained devices, then the size of its code and
+# read-only data sections is compared with the budget. When a change
+# increases the binary size, the budget must be consciously updated together
+# with that change.
+#
+# The budget is recorded for the `thumbv7em-none-eabihf` target and the
+# stable toolchain, since the size depends on both of them.
+
+set -e
+
+# Budget of the `footprint` crate, expressed in bytes. It leaves a small
+# margin over the measured size for toolchain updates.
+BUDGET=45056
+
+TARGET=thumbv7em-none-eabihf
+
+cargo build --release --manifest-path footprint/Cargo.toml --target "$TARGET" "$@"
+
+BINARY=footprint/target/$TARGET/release/footprint
+SIZE=$(size -A "$BINARY" | awk '$1 == ".text" || $1 == ".rodata" { total += $2 } END { print total }')
+
+echo "footprint: $SIZE / $BUDGET bytes"
+
+if [ "$SIZE" -gt "$BUDGET" ]; then
+    echo "The binary size budget has been exceeded"
+    exit 1
+fi
@@ -0,0 +1,70 @@
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Error, ErrorKind, Result};
+
+/// Access level of a route caller.
+///
+/// Access levels are ordered, so a caller with a higher access level can
+/// invoke all routes which require a lower one.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Serialize, Deserialize,
+)]
+pub enum AccessLevel {
+    /// Any caller, even an unauthenticated one.
+    #[default]
+    Guest,
+    /// An authenticated user of the device.
+    User,
+    /// A device administrator, which can invoke maintenance routes, such as
+    /// a factory reset or a firmware update.
+    Administrator,
+}
+
+impl AccessLevel {
+    /// Checks whether a caller with this [`AccessLevel`] can invoke a route
+    /// which requires the given one.
+    #[must_use]
+    pub const fn allows(self, required: Self) -> bool {
+        self as u8 >= required as u8
+    }
+}
+
+/// Checks whether a caller can invoke a route which requires the given
+/// [`AccessLevel`].
+///
+/// It is invoked by a device before running a route handler.
+///
+/// # Errors
+///
+/// If the caller access level is lower than the required one, an error is
+/// returned.
+pub fn authorize(required: AccessLevel, caller: AccessLevel) -> Result<()> {
+    if caller.allows(required) {
+        Ok(())
+    } else {
+        Err(Error::new(
+            ErrorKind::Authorization,
+            "The caller access level is lower than the one required by the route.",
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::ErrorKind;
+
+    use super::{AccessLevel, authorize};
+
+    #[test]
+    fn test_authorize() {
+        assert!(authorize(AccessLevel::Guest, AccessLevel::Guest).is_ok());
+        assert!(authorize(AccessLevel::User, AccessLevel::Administrator).is_ok());
+        assert_eq!(
+            authorize(AccessLevel::Administrator, AccessLevel::User)
+                .unwrap_err()
+                .kind(),
+            ErrorKind::Authorization
+        );
+        assert!(!AccessLevel::Guest.allows(AccessLevel::User));
+    }
+}
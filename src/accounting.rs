@@ -0,0 +1,461 @@
+use crate::economy::{Cost, CostTimespan, Costs, Roi};
+use crate::energy::EnergyClass;
+use crate::metering::EnergyMeter;
+
+// Seconds in a day.
+const SECONDS_PER_DAY: u64 = 86_400;
+
+/// Returns the number of seconds contained in a [`CostTimespan`].
+///
+/// A month and a year last as their average in the Gregorian calendar.
+#[must_use]
+pub const fn timespan_seconds(timespan: CostTimespan) -> u64 {
+    match timespan {
+        CostTimespan::Week => 7 * SECONDS_PER_DAY,
+        CostTimespan::Month => 2_629_746,
+        CostTimespan::Year => 31_556_952,
+    }
+}
+
+/// Converts an amount referred to a [`CostTimespan`] into the equivalent
+/// amount referred to another [`CostTimespan`].
+///
+/// For example, a cost of 7 USD per week is equivalent to a cost of
+/// about 365 USD per year.
+#[must_use]
+pub fn normalize(amount: f64, from: CostTimespan, to: CostTimespan) -> f64 {
+    amount * seconds_to_f64(timespan_seconds(to)) / seconds_to_f64(timespan_seconds(from))
+}
+
+/// A time-of-use band of a [`Tariff`].
+///
+/// The band bounds are expressed in seconds since midnight. When the start
+/// is greater than the end, the band wraps around midnight.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TariffBand {
+    /// Band start, expressed in seconds since midnight.
+    pub start: u32,
+    /// Band end, expressed in seconds since midnight.
+    pub end: u32,
+    /// Energy price, expressed in USD per kilowatt-hour (kWh).
+    pub price: f64,
+}
+
+impl TariffBand {
+    /// Creates a [`TariffBand`].
+    #[must_use]
+    pub const fn new(start: u32, end: u32, price: f64) -> Self {
+        Self { start, end, price }
+    }
+
+    /// Checks whether the [`TariffBand`] contains the given number of seconds
+    /// since midnight.
+    #[must_use]
+    pub const fn contains(&self, seconds: u32) -> bool {
+        if self.start <= self.end {
+            self.start <= seconds && seconds < self.end
+        } else {
+            seconds >= self.start || seconds < self.end
+        }
+    }
+}
+
+/// An energy tariff.
+///
+/// A tariff has a base price, optionally replaced by the price of the
+/// first [`TariffBand`] containing the time of day. The time of day is
+/// computed from timestamps, so their origin must be a midnight,
+/// for example the Unix epoch adjusted to the local time zone.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Tariff {
+    price: f64,
+    bands: &'static [TariffBand],
+}
+
+impl Tariff {
+    /// Creates a [`Tariff`] with a base price, expressed in USD per
+    /// kilowatt-hour (kWh).
+    #[must_use]
+    pub const fn new(price: f64) -> Self {
+        Self { price, bands: &[] }
+    }
+
+    /// Sets the time-of-use [`TariffBand`]s.
+    #[must_use]
+    pub const fn with_bands(mut self, bands: &'static [TariffBand]) -> Self {
+        self.bands = bands;
+        self
+    }
+
+    /// Returns the base price, expressed in USD per kilowatt-hour (kWh).
+    #[must_use]
+    pub const fn price(&self) -> f64 {
+        self.price
+    }
+
+    /// Returns the time-of-use [`TariffBand`]s.
+    #[must_use]
+    pub const fn bands(&self) -> &'static [TariffBand] {
+        self.bands
+    }
+
+    /// Returns the price, expressed in USD per kilowatt-hour (kWh), applied
+    /// at the given timestamp.
+    #[must_use]
+    pub fn price_at(&self, timestamp: u64) -> f64 {
+        let seconds = time_of_day(timestamp);
+        self.bands
+            .iter()
+            .find(|band| band.contains(seconds))
+            .map_or(self.price, |band| band.price)
+    }
+
+    /// Returns the cost, expressed in USD, of an amount of energy, expressed
+    /// in watt-hours (Wh), consumed within an interval.
+    ///
+    /// The energy is assumed to be consumed uniformly within the interval,
+    /// so an interval crossing several bands is priced proportionally to
+    /// its overlap with each of them. An interval exceeding the greatest
+    /// timestamp is clamped.
+    #[must_use]
+    pub fn cost(&self, start: u64, duration: u32, energy: f64) -> f64 {
+        let end = start.saturating_add(u64::from(duration));
+        if end == start {
+            return self.price_at(start) * energy / 1000.;
+        }
+
+        let mut price = 0.;
+        let mut from = start;
+        while from < end {
+            let to = self.next_change(from).min(end);
+            price += self.price_at(from) * seconds_to_f64(to - from);
+            from = to;
+        }
+
+        price / seconds_to_f64(end - start) * energy / 1000.
+    }
+
+    // Returns the first timestamp after the given one at which the price
+    // might change.
+    fn next_change(&self, timestamp: u64) -> u64 {
+        let seconds = time_of_day(timestamp);
+        let change = self
+            .bands
+            .iter()
+            .flat_map(|band| [band.start, band.end])
+            .filter(|bound| *bound > seconds && u64::from(*bound) < SECONDS_PER_DAY)
+            .min()
+            .map_or(SECONDS_PER_DAY, u64::from);
+        (timestamp - u64::from(seconds)).saturating_add(change)
+    }
+}
+
+/// A cost meter which accumulates the operating costs of a device.
+///
+/// The consumed energy is priced according to a [`Tariff`]. The accumulated
+/// costs are projected over each [`CostTimespan`] and reported as [`Costs`],
+/// which can be added to the [`Economy`](crate::economy::Economy)
+/// information of an [`InfoResponse`](crate::response::InfoResponse).
+#[derive(Debug, Clone)]
+pub struct CostMeter {
+    tariff: Tariff,
+    energy: f64,
+    cost: f64,
+    start: Option<u64>,
+    end: Option<u64>,
+    // Start of the last accounted bucket of an energy meter.
+    accounted: Option<u64>,
+}
+
+impl CostMeter {
+    /// Creates a [`CostMeter`] with the given [`Tariff`].
+    #[must_use]
+    pub const fn new(tariff: Tariff) -> Self {
+        Self {
+            tariff,
+            energy: 0.,
+            cost: 0.,
+            start: None,
+            end: None,
+            accounted: None,
+        }
+    }
+
+    /// Adds an amount of energy, expressed in watt-hours (Wh), consumed
+    /// within an interval.
+    pub fn add_energy(&mut self, start: u64, duration: u32, energy: f64) {
+        let end = start.saturating_add(u64::from(duration));
+        self.energy += energy;
+        self.cost += self.tariff.cost(start, duration, energy);
+        self.start = Some(self.start.map_or(start, |first| first.min(start)));
+        self.end = Some(self.end.map_or(end, |last| last.max(end)));
+    }
+
+    /// Accounts the completed buckets of an [`EnergyMeter`] which have not
+    /// been accounted yet.
+    ///
+    /// The newest bucket is still accumulating energy, so it is accounted
+    /// only when a newer bucket is created.
+    pub fn account<const B: usize>(&mut self, meter: &EnergyMeter<B>) {
+        let Some(newest) = meter.buckets().map(|bucket| bucket.start).max() else {
+            return;
+        };
+
+        let accounted = self.accounted;
+        for bucket in meter.buckets().filter(|bucket| {
+            bucket.start < newest && accounted.is_none_or(|start| bucket.start > start)
+        }) {
+            self.add_energy(bucket.start, bucket.duration, bucket.energy);
+            self.accounted = Some(bucket.start);
+        }
+    }
+
+    /// Returns the [`Tariff`].
+    #[must_use]
+    pub const fn tariff(&self) -> &Tariff {
+        &self.tariff
+    }
+
+    /// Returns the accounted energy, expressed in watt-hours (Wh).
+    #[must_use]
+    pub const fn total_energy(&self) -> f64 {
+        self.energy
+    }
+
+    /// Returns the accumulated cost, expressed in USD.
+    #[must_use]
+    pub const fn total_cost(&self) -> f64 {
+        self.cost
+    }
+
+    /// Returns the number of seconds covered by the accounted energy.
+    #[must_use]
+    pub fn elapsed(&self) -> u64 {
+        match (self.start, self.end) {
+            (Some(start), Some(end)) => end - start,
+            _ => 0,
+        }
+    }
+
+    /// Returns the accumulated cost, expressed in USD, projected over a
+    /// [`CostTimespan`].
+    ///
+    /// When no time has been accounted, the projected cost is zero.
+    #[must_use]
+    pub fn cost_per(&self, timespan: CostTimespan) -> f64 {
+        let elapsed = self.elapsed();
+        if elapsed == 0 {
+            return 0.;
+        }
+        self.cost * seconds_to_f64(timespan_seconds(timespan)) / seconds_to_f64(elapsed)
+    }
+
+    /// Returns the [`Cost`] projected over a [`CostTimespan`], rounded to
+    /// the nearest USD.
+    #[must_use]
+    pub fn cost(&self, timespan: CostTimespan) -> Cost {
+        Cost::new(round(self.cost_per(timespan)), timespan)
+    }
+
+    /// Returns the [`Costs`] projected over a week, a month, and a year.
+    ///
+    /// The capacity `C` must be able to contain three elements, otherwise
+    /// the exceeding [`Cost`]s are discarded.
+    #[must_use]
+    pub fn costs<const C: usize>(&self) -> Costs<C> {
+        Costs::new()
+            .insert(self.cost(CostTimespan::Week))
+            .insert(self.cost(CostTimespan::Month))
+            .insert(self.cost(CostTimespan::Year))
+    }
+
+    /// Returns the [`Roi`] of an investment, expressed in USD, for the given
+    /// [`EnergyClass`].
+    ///
+    /// The yearly savings are the difference between the yearly cost of a
+    /// baseline device, expressed in USD, and the yearly projected cost.
+    /// The number of years is rounded up and saturates at [`u8::MAX`].
+    ///
+    /// When there are no savings, [`None`] is returned.
+    #[must_use]
+    pub fn roi(&self, investment: f64, baseline: f64, energy_class: EnergyClass) -> Option<Roi> {
+        let savings = baseline - self.cost_per(CostTimespan::Year);
+        if savings <= 0. {
+            return None;
+        }
+
+        let years = investment.max(0.) / savings;
+        let rounded = round(years);
+        let rounded = if f64::from(rounded) < years {
+            rounded.saturating_add(1)
+        } else {
+            rounded
+        };
+        Some(Roi::new(
+            u8::try_from(rounded.max(0)).unwrap_or(u8::MAX),
+            energy_class,
+        ))
+    }
+
+    /// Removes all accounted energy and costs, keeping the [`Tariff`].
+    pub fn reset(&mut self) {
+        *self = Self::new(self.tariff);
+    }
+}
+
+// Returns the number of seconds since midnight of a timestamp.
+fn time_of_day(timestamp: u64) -> u32 {
+    u32::try_from(timestamp % SECONDS_PER_DAY).unwrap_or_default()
+}
+
+// Converts a number of seconds into a float.
+//
+// Intervals handled by this module fit in 32 bits, about 136 years,
+// so the conversion is exact.
+fn seconds_to_f64(seconds: u64) -> f64 {
+    f64::from(u32::try_from(seconds).unwrap_or(u32::MAX))
+}
+
+// Rounds a value to the nearest integer, saturating at the `i32` bounds.
+//
+// A float-to-integer cast saturates and maps NaN to zero, so it is
+// well-defined for any value.
+#[allow(clippy::cast_possible_truncation)]
+fn round(value: f64) -> i32 {
+    if value < 0. {
+        (value - 0.5) as i32
+    } else {
+        (value + 0.5) as i32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::device::DeviceInfo;
+    use crate::economy::{Cost, CostTimespan, Economy, Roi, Rois};
+    use crate::energy::EnergyClass;
+    use crate::metering::EnergyMeter;
+    use crate::response::InfoResponse;
+    use crate::serialize;
+
+    use super::{CostMeter, Tariff, TariffBand, normalize, timespan_seconds};
+
+    // Night band from 22:00 to 06:00.
+    const BANDS: &[TariffBand] = &[TariffBand::new(79_200, 21_600, 0.1)];
+    const TARIFF: Tariff = Tariff::new(0.3).with_bands(BANDS);
+
+    fn assert_amount(amount: f64, expected: f64) {
+        assert!(
+            (amount - expected).abs() < 1e-9,
+            "{amount} is not equal to {expected}"
+        );
+    }
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(timespan_seconds(CostTimespan::Week), 604_800);
+        assert_amount(normalize(12., CostTimespan::Year, CostTimespan::Month), 1.);
+        assert_amount(normalize(1., CostTimespan::Week, CostTimespan::Week), 1.);
+    }
+
+    #[test]
+    fn test_tariff() {
+        assert_amount(TARIFF.price_at(0), 0.1);
+        assert_amount(TARIFF.price_at(43_200), 0.3);
+        assert_amount(TARIFF.price_at(86_400 + 82_800), 0.1);
+        assert_amount(Tariff::new(0.2).price_at(0), 0.2);
+
+        // 2 kWh consumed from 05:00 to 07:00, half of them in the night band.
+        assert_amount(TARIFF.cost(18_000, 7200, 2000.), 0.4);
+        // An instantaneous consumption.
+        assert_amount(TARIFF.cost(43_200, 0, 1000.), 0.3);
+        // An interval exceeding the greatest timestamp is clamped.
+        assert_amount(Tariff::new(0.2).cost(u64::MAX - 10, 3600, 1000.), 0.2);
+        assert_amount(Tariff::new(0.2).cost(u64::MAX, 3600, 1000.), 0.2);
+    }
+
+    #[test]
+    fn test_cost_meter() {
+        let mut meter = EnergyMeter::<4>::new(3600);
+        let mut costs = CostMeter::new(TARIFF);
+
+        meter.add_energy(0, 1000.);
+        meter.add_energy(3600, 1000.);
+        // The newest bucket is not accounted yet.
+        costs.account(&meter);
+        assert_amount(costs.total_energy(), 1000.);
+        assert_amount(costs.total_cost(), 0.1);
+
+        meter.add_energy(7200, 1000.);
+        costs.account(&meter);
+        costs.account(&meter);
+        assert_amount(costs.total_energy(), 2000.);
+        assert_amount(costs.total_cost(), 0.2);
+        assert_eq!(costs.elapsed(), 7200);
+
+        // 0.1 USD per hour.
+        assert_amount(costs.cost_per(CostTimespan::Week), 16.8);
+        assert_eq!(
+            costs.cost(CostTimespan::Year),
+            Cost::new(877, CostTimespan::Year)
+        );
+
+        // 1000 USD invested to save about 400 USD per year over a baseline
+        // device.
+        assert_eq!(
+            costs.roi(1000., 1276.6, EnergyClass::A),
+            Some(Roi::new(3, EnergyClass::A))
+        );
+        assert_eq!(costs.roi(1000., 800., EnergyClass::A), None);
+
+        costs.reset();
+        assert_amount(costs.cost_per(CostTimespan::Month), 0.);
+        assert_eq!(costs.tariff(), &TARIFF);
+
+        costs.add_energy(u64::MAX - 10, 3600, 1000.);
+        assert_eq!(costs.elapsed(), 10);
+    }
+
+    #[test]
+    fn test_costs_report() {
+        let mut costs = CostMeter::new(Tariff::new(0.5));
+        costs.add_energy(0, 604_800, 10_000.);
+
+        let economy = Economy::<2, 2>::empty()
+            .costs(costs.costs::<4>())
+            .rois(Rois::<2>::init(
+                costs.roi(50., 500., EnergyClass::B).unwrap(),
+            ));
+        let info = DeviceInfo::empty().add_economy(economy);
+
+        assert_eq!(
+            serialize(InfoResponse::new(info)),
+            json!({
+                "economy": {
+                    "costs": [
+                        {
+                            "usd": 5,
+                            "timespan": "Week"
+                        },
+                        {
+                            "usd": 22,
+                            "timespan": "Month"
+                        },
+                        {
+                            "usd": 261,
+                            "timespan": "Year"
+                        }
+                    ],
+                    "roi": [
+                        {
+                            "years": 1,
+                            "energy-class": "B"
+                        }
+                    ]
+                }
+            })
+        );
+    }
+}
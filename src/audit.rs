@@ -0,0 +1,366 @@
+use heapless::Deque;
+
+use serde::Serialize;
+
+use crate::access::AccessLevel;
+use crate::collections::{Backing, SerialLinearMap};
+use crate::hazards::Hazards;
+use crate::parameters::{ParameterValue, Parameters};
+use crate::response::{ErrorKind, ResponseKind};
+use crate::route::{RestKind, Route};
+use crate::string::String;
+
+/// Route through which a controller fetches the [`AuditLog`].
+pub const AUDIT_ROUTE: &str = "/audit";
+
+/// Creates the `GET` [`Route`] through which a controller fetches the
+/// [`AuditLog`].
+///
+/// Since the log reveals who invoked which route, the route requires the
+/// [`AccessLevel::Administrator`] level, which can be lowered through
+/// [`Route::with_access_level`].
+///
+/// Its capacities are inferred from the collection of routes it is added to.
+#[must_use]
+#[inline]
+pub fn audit_route<const H: usize, const P: usize, B: Backing>() -> Route<H, P, B> {
+    Route::get(AUDIT_ROUTE)
+        .description("Audit log of route invocations")
+        .with_access_level(AccessLevel::Administrator)
+        .with_hazards(Hazards::new())
+        .with_parameters(Parameters::new())
+}
+
+/// A record of a route invocation.
+///
+/// A route is identified by its name and its [`RestKind`], as a
+/// [`RouteConfig`](crate::route::RouteConfig). A record contains at most
+/// `P` parameter values and a caller identifier of `S` bytes. When more
+/// parameter values are added, the record is marked as truncated.
+#[derive(Debug, Serialize)]
+pub struct AuditEntry<const P: usize, const S: usize> {
+    /// Invocation timestamp, expressed in seconds.
+    timestamp: u64,
+    /// Route.
+    route: &'static str,
+    /// **_REST_** kind.
+    #[serde(rename = "REST kind")]
+    rest_kind: RestKind,
+    /// Caller identifier.
+    #[serde(skip_serializing_if = "String::is_empty")]
+    caller: String<S>,
+    /// Input parameter values.
+    #[serde(skip_serializing_if = "SerialLinearMap::is_empty")]
+    parameters: SerialLinearMap<&'static str, ParameterValue, P>,
+    /// Whether some input parameter values have been discarded.
+    #[serde(rename = "truncated parameters")]
+    #[serde(skip_serializing_if = "core::ops::Not::not")]
+    truncated_parameters: bool,
+    /// Response kind.
+    #[serde(rename = "response kind")]
+    response_kind: ResponseKind,
+    /// Error returned by the invocation.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<ErrorKind>,
+}
+
+impl<const P: usize, const S: usize> AuditEntry<P, S> {
+    /// Creates an [`AuditEntry`] for a route invoked at the given timestamp.
+    #[must_use]
+    pub const fn new(timestamp: u64, route: &'static str, rest_kind: RestKind) -> Self {
+        Self {
+            timestamp,
+            route,
+            rest_kind,
+            caller: String::empty(),
+            parameters: SerialLinearMap::new(),
+            truncated_parameters: false,
+            response_kind: ResponseKind::Ok,
+            error: None,
+        }
+    }
+
+    /// Sets the caller identifier.
+    ///
+    /// An identifier greater than `S` bytes is truncated.
+    #[must_use]
+    #[inline]
+    pub fn with_caller(mut self, caller: &str) -> Self {
+        self.caller = String::truncated(caller);
+        self
+    }
+
+    /// Adds an input parameter value.
+    ///
+    /// If `P` values have already been added, the value is discarded and
+    /// the [`AuditEntry`] is marked as truncated.
+    #[must_use]
+    #[inline]
+    pub fn with_parameter(mut self, name: &'static str, value: ParameterValue) -> Self {
+        if self.parameters.try_add(name, value).is_err() {
+            self.truncated_parameters = true;
+        }
+        self
+    }
+
+    /// Sets the [`ResponseKind`] returned by the invocation.
+    #[must_use]
+    pub const fn with_response_kind(mut self, response_kind: ResponseKind) -> Self {
+        self.response_kind = response_kind;
+        self
+    }
+
+    /// Sets the [`ErrorKind`] returned by a failed invocation.
+    #[must_use]
+    pub const fn with_error(mut self, error: ErrorKind) -> Self {
+        self.error = Some(error);
+        self
+    }
+
+    /// Returns the invocation timestamp, expressed in seconds.
+    #[must_use]
+    pub const fn timestamp(&self) -> u64 {
+        self.timestamp
+    }
+
+    /// Returns route.
+    #[must_use]
+    pub const fn route(&self) -> &'static str {
+        self.route
+    }
+
+    /// Returns [`RestKind`].
+    #[must_use]
+    pub const fn kind(&self) -> RestKind {
+        self.rest_kind
+    }
+
+    /// Returns the caller identifier.
+    #[must_use]
+    pub fn caller(&self) -> &str {
+        self.caller.as_str()
+    }
+
+    /// Returns the value of an input parameter.
+    #[must_use]
+    pub fn parameter(&self, name: &str) -> Option<&ParameterValue> {
+        self.parameters
+            .iter()
+            .find_map(|(key, value)| (*key == name).then_some(value))
+    }
+
+    /// Checks whether some input parameter values have been discarded.
+    #[must_use]
+    pub const fn truncated_parameters(&self) -> bool {
+        self.truncated_parameters
+    }
+
+    /// Returns the [`ResponseKind`] returned by the invocation.
+    #[must_use]
+    pub const fn response_kind(&self) -> ResponseKind {
+        self.response_kind
+    }
+
+    /// Returns the [`ErrorKind`] returned by a failed invocation.
+    #[must_use]
+    pub const fn error(&self) -> Option<&ErrorKind> {
+        self.error.as_ref()
+    }
+
+    /// Checks whether the invocation succeeded.
+    #[must_use]
+    pub const fn succeeded(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+/// A fixed-capacity log of route invocations.
+///
+/// The log is a ring buffer of `L` [`AuditEntry`]s which does not need any
+/// persistent storage. When it is full, the oldest entry is overwritten and
+/// the number of overwritten entries is reported.
+///
+/// A controller fetches the serialized log through the [`audit_route`].
+#[derive(Debug, Serialize)]
+pub struct AuditLog<const P: usize, const S: usize, const L: usize> {
+    /// Entries, from the oldest to the newest.
+    entries: Deque<AuditEntry<P, S>, L>,
+    /// Number of overwritten entries.
+    overwritten: u32,
+}
+
+impl<const P: usize, const S: usize, const L: usize> Default for AuditLog<P, S, L> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const P: usize, const S: usize, const L: usize> AuditLog<P, S, L> {
+    /// Creates an empty [`AuditLog`].
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            entries: Deque::new(),
+            overwritten: 0,
+        }
+    }
+
+    /// Records an [`AuditEntry`].
+    ///
+    /// If the log is full, the oldest entry is overwritten.
+    pub fn record(&mut self, entry: AuditEntry<P, S>) {
+        if self.entries.is_full() && self.entries.pop_front().is_some() {
+            self.overwritten = self.overwritten.saturating_add(1);
+        }
+        // A zero-capacity log overwrites all entries.
+        if self.entries.push_back(entry).is_err() {
+            self.overwritten = self.overwritten.saturating_add(1);
+        }
+    }
+
+    /// Returns an iterator over the [`AuditEntry`]s, from the oldest to the
+    /// newest.
+    pub fn iter(&self) -> impl Iterator<Item = &AuditEntry<P, S>> {
+        self.entries.iter()
+    }
+
+    /// Returns an iterator over the [`AuditEntry`]s of a route, from the
+    /// oldest to the newest.
+    pub fn route_entries(
+        &self,
+        route: &str,
+        rest_kind: RestKind,
+    ) -> impl Iterator<Item = &AuditEntry<P, S>> {
+        self.entries
+            .iter()
+            .filter(move |entry| entry.route == route && entry.rest_kind == rest_kind)
+    }
+
+    /// Returns the number of [`AuditEntry`]s.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Checks whether the [`AuditLog`] is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the number of overwritten [`AuditEntry`]s.
+    #[must_use]
+    pub const fn overwritten(&self) -> u32 {
+        self.overwritten
+    }
+
+    /// Removes all [`AuditEntry`]s and resets the number of overwritten
+    /// ones.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.overwritten = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::access::AccessLevel;
+    use crate::hazards::Hazards;
+    use crate::parameters::{ParameterValue, Parameters};
+    use crate::response::{ErrorKind, ResponseKind};
+    use crate::route::{RestKind, Route, RouteConfigsBuilder};
+    use crate::serialize;
+
+    use super::{AUDIT_ROUTE, AuditEntry, AuditLog, audit_route};
+
+    #[test]
+    fn test_audit_log() {
+        let mut log = AuditLog::<2, 8, 2>::new();
+
+        log.record(AuditEntry::new(10, "/on", RestKind::Put).with_caller("controller-1"));
+        log.record(
+            AuditEntry::new(20, "/brightness", RestKind::Put)
+                .with_parameter("value", ParameterValue::U8(40))
+                .with_parameter("fade", ParameterValue::Bool(true))
+                .with_parameter("discarded", ParameterValue::F32(1.)),
+        );
+        log.record(
+            AuditEntry::new(30, "/on", RestKind::Put)
+                .with_response_kind(ResponseKind::Serial)
+                .with_error(ErrorKind::Internal),
+        );
+
+        // The oldest entry has been overwritten.
+        assert_eq!(log.len(), 2);
+        assert_eq!(log.overwritten(), 1);
+
+        let entry = log.iter().next().unwrap();
+        assert_eq!(entry.parameter("value"), Some(&ParameterValue::U8(40)));
+        assert!(entry.parameter("discarded").is_none());
+        assert!(entry.truncated_parameters());
+        assert!(entry.succeeded());
+
+        {
+            let mut entries = log.route_entries("/on", RestKind::Put);
+            assert_eq!(entries.next().map(AuditEntry::timestamp), Some(30));
+            assert!(entries.next().is_none());
+        }
+        assert!(log.route_entries("/on", RestKind::Get).next().is_none());
+
+        assert_eq!(
+            serialize(&log),
+            json!({
+                "entries": [
+                    {
+                        "timestamp": 20,
+                        "route": "/brightness",
+                        "REST kind": "Put",
+                        "parameters": {
+                            "value": 40,
+                            "fade": true
+                        },
+                        "truncated parameters": true,
+                        "response kind": "Ok"
+                    },
+                    {
+                        "timestamp": 30,
+                        "route": "/on",
+                        "REST kind": "Put",
+                        "response kind": "Serial",
+                        "error": "Internal"
+                    }
+                ],
+                "overwritten": 1
+            })
+        );
+
+        log.clear();
+        assert!(log.is_empty());
+        assert_eq!(log.overwritten(), 0);
+    }
+
+    #[test]
+    fn test_audit_route() {
+        let route_configs = RouteConfigsBuilder::<2, 2, 2>::new()
+            .route(audit_route())
+            .unwrap()
+            .route(
+                Route::put("/on")
+                    .with_hazards(Hazards::new())
+                    .with_parameters(Parameters::new()),
+            )
+            .unwrap()
+            .build();
+
+        let audit = route_configs.iter().next().unwrap();
+        assert_eq!(audit.route(), AUDIT_ROUTE);
+        assert_eq!(audit.kind(), RestKind::Get);
+        assert_eq!(audit.access_level(), AccessLevel::Administrator);
+
+        let entry = AuditEntry::<2, 16>::new(0, audit.route(), audit.kind())
+            .with_caller("a-very-long-caller");
+        assert_eq!(entry.caller(), "a-very-long-call");
+    }
+}
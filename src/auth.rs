@@ -0,0 +1,438 @@
+use heapless::Deque;
+
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+use crate::access::AccessLevel;
+use crate::error::{Error, ErrorKind, Result};
+use crate::route::RestKind;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Size of a request [`Signature`], expressed in bytes.
+pub const SIGNATURE_SIZE: usize = 32;
+
+/// An `HMAC-SHA256` request signature.
+pub type Signature = [u8; SIGNATURE_SIZE];
+
+/// Decodes a [`Signature`] from its hexadecimal representation.
+///
+/// # Errors
+///
+/// If the text is not a hexadecimal representation of [`SIGNATURE_SIZE`]
+/// bytes, an error is returned.
+pub fn decode_signature(text: &str) -> Result<Signature> {
+    let invalid = || {
+        Error::new(
+            ErrorKind::Authentication,
+            "The request signature is not a valid hexadecimal text.",
+        )
+    };
+
+    if text.len() != 2 * SIGNATURE_SIZE {
+        return Err(invalid());
+    }
+
+    let mut signature = [0; SIGNATURE_SIZE];
+    for (byte, digits) in signature.iter_mut().zip(text.as_bytes().chunks_exact(2)) {
+        let high = hex_digit(digits[0]).ok_or_else(invalid)?;
+        let low = hex_digit(digits[1]).ok_or_else(invalid)?;
+        *byte = (high << 4) | low;
+    }
+    Ok(signature)
+}
+
+/// A request signed by a controller with a pre-shared key.
+///
+/// The signature covers the request **_REST_** kind, route, parameters,
+/// timestamp and nonce. Each text field is preceded by its length as a
+/// big-endian `u32`, the parameters are preceded by their number as a
+/// big-endian `u32`, while timestamp and nonce are big-endian `u64`s, so two
+/// different requests never produce the same signed message.
+///
+/// Parameters are signed in the same order in which they are sent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SignedRequest<'a> {
+    /// **_REST_** kind.
+    pub rest_kind: RestKind,
+    /// Route.
+    pub route: &'a str,
+    /// Parameter names and values, as sent by the controller.
+    pub parameters: &'a [(&'a str, &'a str)],
+    /// Timestamp, expressed in seconds since the Unix epoch.
+    pub timestamp: u64,
+    /// A number used only once.
+    pub nonce: u64,
+}
+
+impl<'a> SignedRequest<'a> {
+    /// Creates a [`SignedRequest`] without parameters.
+    #[must_use]
+    pub const fn new(rest_kind: RestKind, route: &'a str, timestamp: u64, nonce: u64) -> Self {
+        Self {
+            rest_kind,
+            route,
+            parameters: &[],
+            timestamp,
+            nonce,
+        }
+    }
+
+    /// Sets the parameter names and values.
+    #[must_use]
+    pub const fn with_parameters(mut self, parameters: &'a [(&'a str, &'a str)]) -> Self {
+        self.parameters = parameters;
+        self
+    }
+
+    /// Computes the [`Signature`] of the request with a pre-shared key.
+    ///
+    /// It is used by a controller to sign a request.
+    #[must_use]
+    pub fn sign(&self, key: &[u8]) -> Signature {
+        self.mac(key).finalize().into_bytes().into()
+    }
+
+    fn mac(&self, key: &[u8]) -> HmacSha256 {
+        // HMAC accepts keys of any length.
+        let Ok(mut mac) = HmacSha256::new_from_slice(key) else {
+            unreachable!()
+        };
+
+        update_text(&mut mac, rest_kind_name(self.rest_kind));
+        update_text(&mut mac, self.route);
+        update_length(&mut mac, self.parameters.len());
+        for (name, value) in self.parameters {
+            update_text(&mut mac, name);
+            update_text(&mut mac, value);
+        }
+        mac.update(&self.timestamp.to_be_bytes());
+        mac.update(&self.nonce.to_be_bytes());
+        mac
+    }
+}
+
+/// A fixed-capacity cache of the last `C` accepted nonces.
+///
+/// When the cache is full, the oldest nonce is discarded. To prevent the
+/// replay of a request whose nonce has been discarded, all requests with a
+/// timestamp not greater than the greatest timestamp of the discarded nonces
+/// are rejected. Nonces are discarded in insertion order, so a later
+/// discarded nonce with a smaller timestamp never lowers that bound.
+#[derive(Debug, Clone)]
+pub struct NonceCache<const C: usize> {
+    // Accepted nonces together with their timestamps.
+    nonces: Deque<(u64, u64), C>,
+    // Greatest timestamp of a discarded nonce.
+    floor: Option<u64>,
+}
+
+impl<const C: usize> Default for NonceCache<C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const C: usize> NonceCache<C> {
+    /// Creates an empty [`NonceCache`].
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            nonces: Deque::new(),
+            floor: None,
+        }
+    }
+
+    /// Checks whether a request with the given nonce and timestamp might be
+    /// a replay.
+    #[must_use]
+    pub fn is_replay(&self, nonce: u64, timestamp: u64) -> bool {
+        self.floor.is_some_and(|floor| timestamp <= floor)
+            || self.nonces.iter().any(|(other, _)| *other == nonce)
+    }
+
+    /// Inserts the nonce and the timestamp of an accepted request.
+    ///
+    /// If the cache is full, the oldest nonce is discarded.
+    pub fn insert(&mut self, nonce: u64, timestamp: u64) {
+        if self.nonces.is_full()
+            && let Some((_, discarded)) = self.nonces.pop_front()
+        {
+            self.discard(discarded);
+        }
+        // A zero-capacity cache discards all nonces.
+        if self.nonces.push_back((nonce, timestamp)).is_err() {
+            self.discard(timestamp);
+        }
+    }
+
+    /// Returns the number of cached nonces.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.nonces.len()
+    }
+
+    /// Checks whether the [`NonceCache`] is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.nonces.is_empty()
+    }
+
+    fn discard(&mut self, timestamp: u64) {
+        self.floor = Some(self.floor.map_or(timestamp, |floor| floor.max(timestamp)));
+    }
+}
+
+/// An authenticator of [`SignedRequest`]s.
+///
+/// A request is accepted when its signature has been computed with the
+/// device pre-shared key, its timestamp differs from the current time by at
+/// most a tolerance, and its nonce has not been used before. The last `C`
+/// nonces are kept in a [`NonceCache`].
+///
+/// A verified caller is granted the [`AccessLevel`] associated with the
+/// pre-shared key, which is [`AccessLevel::Administrator`] by default.
+/// A device which shares a different key with each kind of caller uses an
+/// [`Authenticator`] for each key.
+///
+/// A failed verification returns an [`Error`] of kind
+/// [`ErrorKind::Authentication`], which is converted into a dedicated
+/// [`ErrorResponse`](crate::response::ErrorResponse) reporting invalid data.
+#[derive(Debug, Clone)]
+pub struct Authenticator<'a, const C: usize> {
+    key: &'a [u8],
+    tolerance: u64,
+    access_level: AccessLevel,
+    nonces: NonceCache<C>,
+}
+
+impl<'a, const C: usize> Authenticator<'a, C> {
+    /// Creates an [`Authenticator`] with a pre-shared key and a timestamp
+    /// tolerance, expressed in seconds.
+    #[must_use]
+    pub const fn new(key: &'a [u8], tolerance: u64) -> Self {
+        Self {
+            key,
+            tolerance,
+            access_level: AccessLevel::Administrator,
+            nonces: NonceCache::new(),
+        }
+    }
+
+    /// Sets the [`AccessLevel`] granted to the callers which own the
+    /// pre-shared key.
+    #[must_use]
+    pub const fn with_access_level(mut self, access_level: AccessLevel) -> Self {
+        self.access_level = access_level;
+        self
+    }
+
+    /// Returns the timestamp tolerance, expressed in seconds.
+    #[must_use]
+    pub const fn tolerance(&self) -> u64 {
+        self.tolerance
+    }
+
+    /// Returns the [`AccessLevel`] granted to the callers which own the
+    /// pre-shared key.
+    #[must_use]
+    pub const fn access_level(&self) -> AccessLevel {
+        self.access_level
+    }
+
+    /// Returns the [`NonceCache`].
+    #[must_use]
+    pub const fn nonces(&self) -> &NonceCache<C> {
+        &self.nonces
+    }
+
+    /// Verifies a [`SignedRequest`] received at the given time, expressed in
+    /// seconds since the Unix epoch.
+    ///
+    /// The nonce of an accepted request is cached, so the same request is
+    /// rejected when received again. The [`AccessLevel`] of the verified
+    /// caller is returned.
+    ///
+    /// # Errors
+    ///
+    /// If the signature is not valid, if the timestamp is outside the
+    /// tolerance, or if the request is a replay, an error is returned.
+    pub fn verify(
+        &mut self,
+        request: &SignedRequest,
+        signature: &[u8],
+        now: u64,
+    ) -> Result<AccessLevel> {
+        // The signature is verified in constant time.
+        if request.mac(self.key).verify_slice(signature).is_err() {
+            return Err(Error::new(
+                ErrorKind::Authentication,
+                "The request signature is not valid.",
+            ));
+        }
+
+        if request.timestamp.abs_diff(now) > self.tolerance {
+            return Err(Error::new(
+                ErrorKind::Authentication,
+                "The request timestamp is outside the accepted tolerance.",
+            ));
+        }
+
+        if self.nonces.is_replay(request.nonce, request.timestamp) {
+            return Err(Error::new(
+                ErrorKind::Authentication,
+                "The request has already been received.",
+            ));
+        }
+
+        self.nonces.insert(request.nonce, request.timestamp);
+        Ok(self.access_level)
+    }
+}
+
+const fn rest_kind_name(rest_kind: RestKind) -> &'static str {
+    match rest_kind {
+        RestKind::Get => "GET",
+        RestKind::Put => "PUT",
+        RestKind::Post => "POST",
+        RestKind::Delete => "DELETE",
+    }
+}
+
+fn update_length(mac: &mut HmacSha256, length: usize) {
+    mac.update(&u32::try_from(length).unwrap_or(u32::MAX).to_be_bytes());
+}
+
+fn update_text(mac: &mut HmacSha256, text: &str) {
+    update_length(mac, text.len());
+    mac.update(text.as_bytes());
+}
+
+const fn hex_digit(digit: u8) -> Option<u8> {
+    match digit {
+        b'0'..=b'9' => Some(digit - b'0'),
+        b'a'..=b'f' => Some(digit - b'a' + 10),
+        b'A'..=b'F' => Some(digit - b'A' + 10),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::access::AccessLevel;
+    use crate::error::ErrorKind;
+    use crate::response::{ErrorKind as ResponseErrorKind, ErrorResponse};
+    use crate::route::RestKind;
+
+    use super::{Authenticator, NonceCache, SignedRequest, decode_signature};
+
+    const KEY: &[u8] = b"device pre-shared key";
+    const HEX: &[u8] = b"0123456789ABCDEF";
+
+    #[test]
+    fn test_signature() {
+        let request = SignedRequest::new(RestKind::Put, "/light/on", 1000, 1)
+            .with_parameters(&[("brightness", "40")]);
+        let signature = request.sign(KEY);
+
+        // Any change of the request changes the signature.
+        assert_ne!(
+            SignedRequest::new(RestKind::Put, "/light/on", 1000, 1)
+                .with_parameters(&[("brightness", "41")])
+                .sign(KEY),
+            signature
+        );
+        assert_ne!(
+            SignedRequest::new(RestKind::Post, "/light/on", 1000, 1)
+                .with_parameters(&[("brightness", "40")])
+                .sign(KEY),
+            signature
+        );
+        // Fields cannot be shifted among each other.
+        assert_ne!(
+            SignedRequest::new(RestKind::Put, "/light/on", 1000, 1)
+                .with_parameters(&[("brightness4", "0")])
+                .sign(KEY),
+            signature
+        );
+        assert_ne!(request.sign(b"another key"), signature);
+
+        // Hexadecimal signatures are decoded.
+        let mut hex = [0; 64];
+        for (digits, byte) in hex.chunks_exact_mut(2).zip(signature) {
+            digits.copy_from_slice(&[HEX[usize::from(byte >> 4)], HEX[usize::from(byte & 15)]]);
+        }
+        assert_eq!(
+            decode_signature(core::str::from_utf8(&hex).unwrap()).unwrap(),
+            signature
+        );
+        assert_eq!(
+            decode_signature("0g").unwrap_err().kind(),
+            ErrorKind::Authentication
+        );
+    }
+
+    #[test]
+    fn test_authenticator() {
+        let mut authenticator =
+            Authenticator::<2>::new(KEY, 30).with_access_level(AccessLevel::User);
+        let request = SignedRequest::new(RestKind::Get, "/light", 1000, 7);
+
+        assert_eq!(
+            authenticator
+                .verify(&request, &request.sign(KEY), 1010)
+                .unwrap(),
+            AccessLevel::User
+        );
+
+        // Replayed request.
+        let error = authenticator
+            .verify(&request, &request.sign(KEY), 1010)
+            .unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::Authentication);
+
+        // Forged signature.
+        assert!(
+            authenticator
+                .verify(&request, &request.sign(b"forged key"), 1010)
+                .is_err()
+        );
+
+        // Expired timestamp.
+        let expired = SignedRequest::new(RestKind::Get, "/light", 900, 8);
+        assert!(
+            authenticator
+                .verify(&expired, &expired.sign(KEY), 1010)
+                .is_err()
+        );
+
+        // A failed verification is reported as invalid data.
+        let response = ErrorResponse::<64>::from(error);
+        assert_eq!(response.error, ResponseErrorKind::InvalidData);
+        assert_eq!(response.code, Some(ErrorKind::Authentication.code()));
+    }
+
+    #[test]
+    fn test_nonce_cache() {
+        let mut nonces = NonceCache::<2>::new();
+        nonces.insert(1, 100);
+        nonces.insert(2, 110);
+        assert!(nonces.is_replay(1, 120));
+
+        // The nonce `1` is discarded, so older requests are rejected.
+        nonces.insert(3, 105);
+        assert_eq!(nonces.len(), 2);
+        assert!(nonces.is_replay(1, 100));
+        assert!(!nonces.is_replay(1, 101));
+        assert!(!nonces.is_replay(4, 120));
+
+        // A discarded nonce with a smaller timestamp does not lower the
+        // bound.
+        nonces.insert(4, 120);
+        assert!(nonces.is_replay(5, 110));
+        assert!(!nonces.is_replay(5, 111));
+        nonces.insert(5, 130);
+        assert!(nonces.is_replay(6, 110));
+        assert!(!nonces.is_replay(6, 121));
+    }
+}
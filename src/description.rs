@@ -0,0 +1,174 @@
+use serde::ser::{Serialize, SerializeMap, Serializer};
+
+/// A fixed table of localized descriptions.
+///
+/// Each entry associates a language tag, such as `en` or `it-IT`,
+/// with a description text. It is serialized as a map.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Descriptions(&'static [(&'static str, &'static str)]);
+
+impl Serialize for Descriptions {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.0.len()))?;
+        for (language, text) in self.0 {
+            map.serialize_entry(language, text)?;
+        }
+        map.end()
+    }
+}
+
+impl Descriptions {
+    /// Creates an empty [`Descriptions`].
+    #[must_use]
+    pub const fn empty() -> Self {
+        Self(&[])
+    }
+
+    /// Creates a [`Descriptions`] from a table of `(language tag, text)`.
+    #[must_use]
+    pub const fn new(descriptions: &'static [(&'static str, &'static str)]) -> Self {
+        Self(descriptions)
+    }
+
+    /// Checks whether [`Descriptions`] is empty.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the description associated with a language tag.
+    ///
+    /// Language tags are compared case-insensitively.
+    #[must_use]
+    pub fn get(&self, language: &str) -> Option<&'static str> {
+        self.0
+            .iter()
+            .find_map(|(tag, text)| tag.eq_ignore_ascii_case(language).then_some(*text))
+    }
+
+    /// Returns the description which best matches an `Accept-Language`
+    /// header value.
+    ///
+    /// Language ranges are evaluated by their quality values, while ranges
+    /// with an invalid quality are ignored. Among ranges
+    /// with the same quality, an exact language tag match is preferred to
+    /// a primary language match, such as `en-US` for `en`. The `*` range
+    /// matches the first description.
+    ///
+    /// If no description matches, `None` is returned.
+    #[must_use]
+    pub fn best_match(&self, accept_language: &str) -> Option<&'static str> {
+        let mut best: Option<(f32, u8, &'static str)> = None;
+
+        for range in accept_language.split(',') {
+            let mut parts = range.split(';');
+            let language = parts.next().unwrap_or_default().trim();
+            let quality = match parts.find_map(|part| part.trim().strip_prefix("q=")) {
+                // Quality values must lie between 0 and 1.
+                Some(quality) => match quality.trim().parse::<f32>() {
+                    Ok(quality) if (0.0..=1.0).contains(&quality) => quality,
+                    _ => continue,
+                },
+                None => 1.0,
+            };
+
+            if language.is_empty() || quality <= 0.0 {
+                continue;
+            }
+
+            let Some((specificity, text)) = self.matching(language) else {
+                continue;
+            };
+
+            if best.is_none_or(|(best_quality, best_specificity, _)| {
+                quality
+                    .total_cmp(&best_quality)
+                    .then(specificity.cmp(&best_specificity))
+                    .is_gt()
+            }) {
+                best = Some((quality, specificity, text));
+            }
+        }
+
+        best.map(|(_, _, text)| text)
+    }
+
+    // Returns the description matching a language range together with
+    // the match specificity.
+    fn matching(&self, language: &str) -> Option<(u8, &'static str)> {
+        if language == "*" {
+            return self.0.first().map(|(_, text)| (0, *text));
+        }
+
+        if let Some(text) = self.get(language) {
+            return Some((2, text));
+        }
+
+        let primary = primary_language(language);
+        self.0.iter().find_map(|(tag, text)| {
+            primary_language(tag)
+                .eq_ignore_ascii_case(primary)
+                .then_some((1, *text))
+        })
+    }
+}
+
+fn primary_language(language: &str) -> &str {
+    language.split('-').next().unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::serialize;
+
+    use super::Descriptions;
+
+    const DESCRIPTIONS: Descriptions = Descriptions::new(&[
+        ("en", "Turns the light on"),
+        ("it-IT", "Accende la luce"),
+        ("de", "Schaltet das Licht ein"),
+    ]);
+
+    #[test]
+    fn test_serialize_descriptions() {
+        assert_eq!(
+            serialize(DESCRIPTIONS),
+            json!({
+                "en": "Turns the light on",
+                "it-IT": "Accende la luce",
+                "de": "Schaltet das Licht ein",
+            })
+        );
+    }
+
+    #[test]
+    fn test_best_match() {
+        assert_eq!(DESCRIPTIONS.best_match("it-IT"), Some("Accende la luce"));
+        assert_eq!(DESCRIPTIONS.best_match("IT"), Some("Accende la luce"));
+        assert_eq!(
+            DESCRIPTIONS.best_match("fr-CH, fr;q=0.9, de;q=0.7, en;q=0.8"),
+            Some("Turns the light on")
+        );
+        assert_eq!(
+            DESCRIPTIONS.best_match("en-GB;q=0.5, de-AT;q=0.5, de;q=0.5"),
+            Some("Schaltet das Licht ein")
+        );
+        assert_eq!(
+            DESCRIPTIONS.best_match("fr, *;q=0.1"),
+            Some("Turns the light on")
+        );
+        assert_eq!(DESCRIPTIONS.best_match("fr, es;q=0.5"), None);
+        // Invalid qualities are ignored.
+        assert_eq!(
+            DESCRIPTIONS.best_match("de;q=nan, en;q=0.5"),
+            Some("Turns the light on")
+        );
+        assert_eq!(
+            DESCRIPTIONS.best_match("de;q=inf, it;q=2, en;q=0.1"),
+            Some("Turns the light on")
+        );
+        assert_eq!(Descriptions::empty().best_match("*"), None);
+    }
+}
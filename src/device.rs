@@ -1,11 +1,18 @@
 use serde::{Deserialize, Serialize};
 
-use crate::economy::Economy;
-use crate::energy::Energy;
-use crate::route::RouteConfigs;
+use crate::collections::{Backing, Hashed};
+use crate::economy::{Economy, EconomyViolation};
+use crate::energy::{Energy, EnergyViolation};
+use crate::error::Error;
+use crate::route::{RouteConfigs, RouteDescriptor};
+use crate::string::String;
+use crate::version::{SCHEMA_VERSION, Version};
 
 pub use tosca::device::{DeviceEnvironment, DeviceKind};
 
+#[cfg(feature = "macros")]
+pub use tosca_stack_macros::{device, device_descriptor};
+
 /// Device information.
 #[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
 pub struct DeviceInfo<const C: usize, const R: usize, const E: usize, const CF: usize> {
@@ -19,18 +26,24 @@ pub struct DeviceInfo<const C: usize, const R: usize, const E: usize, const CF:
     pub energy: Energy<E, CF>,
 }
 
-impl DeviceInfo<2, 2, 2, 2> {
-    /// Creates a [`DeviceInfo`].
+impl DeviceInfo<0, 0, 0, 0> {
+    /// Creates an empty [`DeviceInfo`].
+    ///
+    /// All capacities are zero until [`Economy`] and [`Energy`] data are
+    /// added, so an empty [`DeviceInfo`] does not waste memory.
     #[must_use]
     pub const fn empty() -> Self {
-        DeviceInfo::<2, 2, 2, 2> {
-            energy: Energy::<2, 2>::empty(),
-            economy: Economy::<2, 2>::empty(),
-        }
+        Self::new(Economy::empty(), Energy::empty())
     }
 }
 
 impl<const C: usize, const R: usize, const E: usize, const CF: usize> DeviceInfo<C, R, E, CF> {
+    /// Creates a [`DeviceInfo`] from [`Economy`] and [`Energy`] data.
+    #[must_use]
+    pub const fn new(economy: Economy<C, R>, energy: Energy<E, CF>) -> Self {
+        Self { economy, energy }
+    }
+
     /// Adds [`Energy`] data.
     #[must_use]
     #[inline]
@@ -56,36 +69,636 @@ impl<const C: usize, const R: usize, const E: usize, const CF: usize> DeviceInfo
             economy,
         }
     }
+
+    /// Validates the consistency of [`Economy`] and [`Energy`] data.
+    ///
+    /// # Errors
+    ///
+    /// The [`InfoViolation`] returned by either [`Economy::validate`] or
+    /// [`Energy::validate`] is returned.
+    pub fn validate(&self) -> core::result::Result<(), InfoViolation> {
+        self.economy.validate()?;
+        self.energy.validate()?;
+        Ok(())
+    }
+}
+
+/// A violation of the consistency of [`DeviceInfo`] data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InfoViolation {
+    /// A violation of [`Economy`] data.
+    Economy(EconomyViolation),
+    /// A violation of [`Energy`] data.
+    Energy(EnergyViolation),
+}
+
+impl From<EconomyViolation> for InfoViolation {
+    fn from(violation: EconomyViolation) -> Self {
+        Self::Economy(violation)
+    }
+}
+
+impl From<EnergyViolation> for InfoViolation {
+    fn from(violation: EnergyViolation) -> Self {
+        Self::Energy(violation)
+    }
+}
+
+impl From<InfoViolation> for Error {
+    fn from(violation: InfoViolation) -> Self {
+        match violation {
+            InfoViolation::Economy(violation) => violation.into(),
+            InfoViolation::Energy(violation) => violation.into(),
+        }
+    }
+}
+
+/// Device identity and firmware information.
+///
+/// It allows a controller to distinguish among identical devices and to
+/// know which firmware they run.
+#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+pub struct DeviceIdentity<const S: usize> {
+    /// Serial number.
+    #[serde(rename = "serial number")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub serial_number: Option<String<S>>,
+    /// Manufacturer.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub manufacturer: Option<String<S>>,
+    /// Model.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub model: Option<String<S>>,
+    /// Firmware version.
+    #[serde(rename = "firmware version")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub firmware_version: Option<Version>,
+    /// Hardware revision.
+    #[serde(rename = "hardware revision")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub hardware_revision: Option<String<S>>,
+}
+
+impl<const S: usize> Default for DeviceIdentity<S> {
+    fn default() -> Self {
+        Self::empty()
+    }
+}
+
+impl<const S: usize> DeviceIdentity<S> {
+    /// Creates an empty [`DeviceIdentity`].
+    #[must_use]
+    pub const fn empty() -> Self {
+        Self {
+            serial_number: None,
+            manufacturer: None,
+            model: None,
+            firmware_version: None,
+            hardware_revision: None,
+        }
+    }
+
+    /// Sets the serial number.
+    #[must_use]
+    #[inline]
+    pub fn serial_number(mut self, serial_number: String<S>) -> Self {
+        self.serial_number = Some(serial_number);
+        self
+    }
+
+    /// Sets the manufacturer.
+    #[must_use]
+    #[inline]
+    pub fn manufacturer(mut self, manufacturer: String<S>) -> Self {
+        self.manufacturer = Some(manufacturer);
+        self
+    }
+
+    /// Sets the model.
+    #[must_use]
+    #[inline]
+    pub fn model(mut self, model: String<S>) -> Self {
+        self.model = Some(model);
+        self
+    }
+
+    /// Sets the firmware [`Version`].
+    #[must_use]
+    pub const fn firmware_version(mut self, firmware_version: Version) -> Self {
+        self.firmware_version = Some(firmware_version);
+        self
+    }
+
+    /// Sets the hardware revision.
+    #[must_use]
+    #[inline]
+    pub fn hardware_revision(mut self, hardware_revision: String<S>) -> Self {
+        self.hardware_revision = Some(hardware_revision);
+        self
+    }
+
+    /// Checks whether [`DeviceIdentity`] is **completely** empty.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.serial_number.is_none()
+            && self.manufacturer.is_none()
+            && self.model.is_none()
+            && self.firmware_version.is_none()
+            && self.hardware_revision.is_none()
+    }
 }
 
 /// Device data.
+///
+/// The [`Backing`] of its route configurations is [`Hashed`] by default.
 #[derive(Debug, Serialize)]
-pub struct DeviceData<const H: usize, const I: usize, const N: usize> {
+#[serde(bound = "")]
+pub struct DeviceData<
+    const H: usize,
+    const I: usize,
+    const N: usize,
+    const S: usize = 0,
+    B: Backing = Hashed,
+> {
+    /// Version of the device description format.
+    #[serde(rename = "schema version")]
+    pub schema_version: Version,
     /// Device kind.
     pub kind: DeviceKind,
     /// Device environment.
     pub environment: DeviceEnvironment,
+    /// Device description.
+    pub description: Option<&'static str>,
     /// Device main route.
     #[serde(rename = "main route")]
     pub main_route: &'static str,
     /// All device route configurations.
-    pub route_configs: RouteConfigs<H, I, N>,
+    pub route_configs: RouteConfigs<H, I, N, B>,
+    /// Number of mandatory routes of the device kind.
+    pub mandatory_routes: u8,
+    /// Device identity.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub identity: Option<DeviceIdentity<S>>,
 }
 
-impl<const H: usize, const I: usize, const N: usize> DeviceData<H, I, N> {
+impl<const H: usize, const I: usize, const N: usize, B: Backing> DeviceData<H, I, N, 0, B> {
     /// Creates a [`DeviceData`].
     #[must_use]
     pub const fn new(
         kind: DeviceKind,
         environment: DeviceEnvironment,
         main_route: &'static str,
-        route_configs: RouteConfigs<H, I, N>,
+        route_configs: RouteConfigs<H, I, N, B>,
     ) -> Self {
         Self {
+            schema_version: SCHEMA_VERSION,
             kind,
             environment,
+            description: None,
             main_route,
             route_configs,
+            mandatory_routes: 0,
+            identity: None,
+        }
+    }
+}
+
+impl<const H: usize, const I: usize, const N: usize, const S: usize, B: Backing>
+    DeviceData<H, I, N, S, B>
+{
+    /// Sets the device description.
+    #[must_use]
+    pub const fn description(mut self, description: &'static str) -> Self {
+        self.description = Some(description);
+        self
+    }
+
+    /// Sets the number of mandatory routes of the device kind.
+    #[must_use]
+    pub const fn with_mandatory_routes(mut self, mandatory_routes: u8) -> Self {
+        self.mandatory_routes = mandatory_routes;
+        self
+    }
+
+    /// Adds [`DeviceIdentity`] data.
+    #[must_use]
+    #[inline]
+    pub fn identity<const S2: usize>(
+        self,
+        identity: DeviceIdentity<S2>,
+    ) -> DeviceData<H, I, N, S2, B> {
+        DeviceData::<H, I, N, S2, B> {
+            schema_version: self.schema_version,
+            kind: self.kind,
+            environment: self.environment,
+            description: self.description,
+            main_route: self.main_route,
+            route_configs: self.route_configs,
+            mandatory_routes: self.mandatory_routes,
+            identity: Some(identity),
         }
     }
 }
+
+/// Device data which can be built in a `const` context.
+///
+/// Its routes are a static slice of [`RouteDescriptor`]s, so the whole
+/// device description can be defined as a `static` item and stored in
+/// read-only memory. It produces the same serialization of [`DeviceData`]
+/// without a [`DeviceIdentity`].
+#[derive(Debug, Serialize)]
+pub struct DeviceDescriptor {
+    /// Version of the device description format.
+    #[serde(rename = "schema version")]
+    pub schema_version: Version,
+    /// Device kind.
+    pub kind: DeviceKind,
+    /// Device environment.
+    pub environment: DeviceEnvironment,
+    /// Device description.
+    pub description: Option<&'static str>,
+    /// Device main route.
+    #[serde(rename = "main route")]
+    pub main_route: &'static str,
+    /// All device routes.
+    #[serde(rename = "route_configs")]
+    pub routes: &'static [RouteDescriptor],
+    /// Number of mandatory routes of the device kind.
+    pub mandatory_routes: u8,
+}
+
+impl DeviceDescriptor {
+    /// Creates a [`DeviceDescriptor`].
+    #[must_use]
+    pub const fn new(
+        kind: DeviceKind,
+        environment: DeviceEnvironment,
+        main_route: &'static str,
+        routes: &'static [RouteDescriptor],
+    ) -> Self {
+        Self {
+            schema_version: SCHEMA_VERSION,
+            kind,
+            environment,
+            description: None,
+            main_route,
+            routes,
+            mandatory_routes: 0,
+        }
+    }
+
+    /// Sets the device description.
+    #[must_use]
+    pub const fn description(mut self, description: &'static str) -> Self {
+        self.description = Some(description);
+        self
+    }
+
+    /// Sets the number of mandatory routes of the device kind.
+    #[must_use]
+    pub const fn with_mandatory_routes(mut self, mandatory_routes: u8) -> Self {
+        self.mandatory_routes = mandatory_routes;
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::economy::{Cost, CostTimespan, Costs, Economy, EconomyViolation};
+    use crate::error::{Error, ErrorKind};
+    use crate::hazards::{Hazard, Hazards};
+    use crate::parameters::{ParameterDescriptor, Parameters};
+    use crate::route::{Route, RouteConfigs, RouteConfigsBuilder, RouteDescriptor};
+    use crate::string::String;
+    use crate::version::{SCHEMA_VERSION, Version};
+    use crate::{deserialize, serialize};
+
+    use super::{
+        DeviceData, DeviceDescriptor, DeviceEnvironment, DeviceIdentity, DeviceInfo, DeviceKind,
+        InfoViolation,
+    };
+
+    const STRING_SIZE: usize = 16;
+
+    #[test]
+    fn test_device_identity() {
+        let identity = DeviceIdentity::<STRING_SIZE>::empty()
+            .serial_number(String::infallible("SN-0001"))
+            .manufacturer(String::infallible("Tosca Labs"))
+            .model(String::infallible("Lamp"))
+            .firmware_version(Version::new(1, 2, 3))
+            .hardware_revision(String::infallible("rev-B"));
+
+        let device_data = DeviceData::new(
+            DeviceKind::Light,
+            DeviceEnvironment::Esp32,
+            "/light",
+            RouteConfigs::<2, 2, 2>::new(),
+        )
+        .identity(identity.clone());
+
+        assert_eq!(
+            serialize(&device_data),
+            json!({
+                "schema version": "0.2.0",
+                "kind": "Light",
+                "environment": "Esp32",
+                "main route": "/light",
+                "description": null,
+                "mandatory_routes": 0,
+                "route_configs": [],
+                "identity": {
+                    "serial number": "SN-0001",
+                    "manufacturer": "Tosca Labs",
+                    "model": "Lamp",
+                    "firmware version": "1.2.3",
+                    "hardware revision": "rev-B"
+                }
+            })
+        );
+
+        assert_eq!(
+            deserialize::<DeviceIdentity<STRING_SIZE>>(serialize(&identity)),
+            identity
+        );
+    }
+
+    #[test]
+    fn test_device_info_validate() {
+        assert!(DeviceInfo::empty().validate().is_ok());
+
+        let info = DeviceInfo::empty().add_economy(Economy::init_with_costs(Costs::<2>::init(
+            Cost::new(-5, CostTimespan::Month),
+        )));
+        let violation = info.validate().unwrap_err();
+        assert_eq!(
+            violation,
+            InfoViolation::Economy(EconomyViolation::NegativeCost(CostTimespan::Month))
+        );
+        assert_eq!(Error::from(violation).kind(), ErrorKind::Economy);
+    }
+
+    // Device data serialized in the wire format of each schema version.
+    fn schema_device_data() -> DeviceData<2, 2, 2> {
+        let route_configs = RouteConfigsBuilder::<2, 2, 2>::new()
+            .route(
+                Route::put("/on")
+                    .description("Turns the light on")
+                    .with_hazards(Hazards::init(Hazard::FireHazard))
+                    .with_parameters(Parameters::new().rangeu64("brightness", (0, 20, 1))),
+            )
+            .unwrap()
+            .build();
+
+        DeviceData::new(
+            DeviceKind::Light,
+            DeviceEnvironment::Esp32,
+            "/light",
+            route_configs,
+        )
+    }
+
+    // The wire format of each schema version is frozen when the version is
+    // released. Any change of the format requires a new schema version and
+    // a new test, leaving the previous ones untouched.
+    #[test]
+    fn test_schema_version_0_1_0() {
+        let schema = json!({
+            "schema version": "0.1.0",
+            "kind": "Light",
+            "environment": "Esp32",
+            "main route": "/light",
+            "route_configs": [
+                {
+                    "name": "/on",
+                    "description": "Turns the light on",
+                    "REST kind": "Put",
+                    "response kind": "Ok",
+                    "hazards": ["FireHazard"],
+                    "parameters": {
+                        "brightness": {
+                            "RangeU64": {
+                                "min": 0,
+                                "max": 20,
+                                "step": 1,
+                                "default": 0
+                            }
+                        }
+                    }
+                }
+            ]
+        });
+
+        // A controller reading the 0.1.0 format detects that it cannot
+        // read the current one.
+        let version = deserialize::<Version>(schema["schema version"].clone());
+        assert_eq!(version, Version::new(0, 1, 0));
+        assert!(!SCHEMA_VERSION.is_compatible_with(&version));
+        assert_ne!(serialize(schema_device_data()), schema);
+    }
+
+    #[test]
+    fn test_schema_version_0_2_0() {
+        assert_eq!(SCHEMA_VERSION, Version::new(0, 2, 0));
+
+        assert_eq!(
+            serialize(schema_device_data()),
+            json!({
+                "schema version": "0.2.0",
+                "kind": "Light",
+                "environment": "Esp32",
+                "main route": "/light",
+                "description": null,
+                "mandatory_routes": 0,
+                "route_configs": [
+                    {
+                        "name": "/on",
+                        "path": "/on",
+                        "description": "Turns the light on",
+                        "REST kind": "Put",
+                        "response kind": "Ok",
+                        "hazards": ["FireHazard"],
+                        "parameters": {
+                            "brightness": {
+                                "RangeU64": {
+                                    "min": 0,
+                                    "max": 20,
+                                    "step": 1,
+                                    "default": 0
+                                }
+                            }
+                        }
+                    }
+                ]
+            })
+        );
+    }
+
+    #[test]
+    fn test_device_descriptor() {
+        static DEVICE: DeviceDescriptor = DeviceDescriptor::new(
+            DeviceKind::Light,
+            DeviceEnvironment::Esp32,
+            "/light",
+            &[RouteDescriptor::put("/on")
+                .description("Turns the light on")
+                .with_hazards(&[Hazard::FireHazard])
+                .with_parameters(&[ParameterDescriptor::rangeu64("brightness", (0, 20, 1))])],
+        );
+
+        let route_configs = RouteConfigsBuilder::<2, 2, 2>::new()
+            .route(
+                Route::put("/on")
+                    .description("Turns the light on")
+                    .with_hazards(Hazards::init(Hazard::FireHazard))
+                    .with_parameters(Parameters::new().rangeu64("brightness", (0, 20, 1))),
+            )
+            .unwrap()
+            .build();
+
+        assert_eq!(
+            serialize(&DEVICE),
+            serialize(DeviceData::new(
+                DeviceKind::Light,
+                DeviceEnvironment::Esp32,
+                "/light",
+                route_configs,
+            ))
+        );
+    }
+
+    #[cfg(feature = "macros")]
+    #[test]
+    fn test_device_macro() {
+        use super::device;
+
+        let (device_data, routes) = device! {
+            kind: Light,
+            environment: Esp32,
+            main_route: "/light",
+            routes: {
+                put "/on" {
+                    description: "Turns the light on",
+                    hazards: [FireHazard, ElectricEnergyConsumption],
+                    parameters: [
+                        rangef64("brightness", (0., 20., 0.1)),
+                        bool("save-energy", false),
+                        u8("mode", 1),
+                    ],
+                },
+                put "/off",
+                get "/on",
+            },
+        };
+
+        assert_eq!(routes.len(), 3);
+        assert_eq!(device_data.route_configs.len(), 3);
+
+        assert_eq!(
+            serialize(device_data),
+            json!({
+                "schema version": "0.2.0",
+                "kind": "Light",
+                "environment": "Esp32",
+                "main route": "/light",
+                "description": null,
+                "mandatory_routes": 0,
+                "route_configs": [
+                    {
+                        "name": "/on",
+                        "path": "/on",
+                        "description": "Turns the light on",
+                        "REST kind": "Put",
+                        "response kind": "Ok",
+                        "hazards": ["FireHazard", "ElectricEnergyConsumption"],
+                        "parameters": {
+                            "brightness": {
+                                "RangeF64": {
+                                    "min": 0.0,
+                                    "max": 20.0,
+                                    "step": 0.1,
+                                    "default": 0.0
+                                }
+                            },
+                            "save-energy": {
+                                "Bool": {
+                                    "default": false
+                                }
+                            },
+                            "mode": {
+                                "U8": {
+                                    "default": 1,
+                                    "min": 0,
+                                    "max": 255
+                                }
+                            }
+                        }
+                    },
+                    {
+                        "name": "/off",
+                        "path": "/off",
+                        "description": null,
+                        "REST kind": "Put",
+                        "response kind": "Ok"
+                    },
+                    {
+                        "name": "/on",
+                        "path": "/on",
+                        "description": null,
+                        "REST kind": "Get",
+                        "response kind": "Ok"
+                    }
+                ]
+            })
+        );
+    }
+    #[cfg(feature = "macros")]
+    #[test]
+    fn test_device_descriptor_macro() {
+        use super::{device, device_descriptor};
+
+        static DEVICE: DeviceDescriptor = device_descriptor! {
+            kind: Light,
+            environment: Esp32,
+            main_route: "/light",
+            routes: {
+                put "/on" {
+                    description: "Turns the light on",
+                    hazards: [FireHazard, ElectricEnergyConsumption],
+                    parameters: [
+                        rangef64("brightness", (0., 20., 0.1)),
+                        bool("save-energy", false),
+                    ],
+                },
+                put "/off",
+            },
+        };
+
+        let (device_data, _) = device! {
+            kind: Light,
+            environment: Esp32,
+            main_route: "/light",
+            routes: {
+                put "/on" {
+                    description: "Turns the light on",
+                    hazards: [FireHazard, ElectricEnergyConsumption],
+                    parameters: [
+                        rangef64("brightness", (0., 20., 0.1)),
+                        bool("save-energy", false),
+                    ],
+                },
+                put "/off",
+            },
+        };
+
+        assert_eq!(DEVICE.routes.len(), 2);
+        assert_eq!(serialize(&DEVICE), serialize(device_data));
+    }
+}
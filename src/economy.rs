@@ -1,9 +1,14 @@
 use serde::{Deserialize, Serialize};
 
 use crate::collections::OutputSet;
+use crate::energy::EnergyClass;
+use crate::error::{Error, ErrorKind};
 
 pub use tosca::economy::{Cost, CostTimespan, Roi};
 
+// Range of valid ROI years.
+const ROI_YEARS: core::ops::RangeInclusive<u8> = 1..=30;
+
 /// A collection of [`Cost`]s.
 pub type Costs<const C: usize> = OutputSet<Cost, C>;
 
@@ -21,31 +26,39 @@ pub struct Economy<const C: usize, const R: usize> {
     pub roi: Option<Rois<R>>,
 }
 
-impl<const C: usize, const R: usize> Economy<C, R> {
-    /// Creates an empty [`Economy`] instance.
+impl<const C: usize> Economy<C, 0> {
+    /// Creates a new [`Economy`] instance initialized with [`Costs`] data.
+    ///
+    /// [`Rois`] have zero capacity until they are added.
     #[must_use]
-    pub const fn empty() -> Self {
+    pub const fn init_with_costs(costs: Costs<C>) -> Self {
         Self {
-            costs: None,
+            costs: Some(costs),
             roi: None,
         }
     }
+}
 
-    /// Creates a new [`Economy`] instance initialized with [`Costs`] data.
+impl<const R: usize> Economy<0, R> {
+    /// Creates a new [`Economy`] instance initialized with [`Rois`] data.
+    ///
+    /// [`Costs`] have zero capacity until they are added.
     #[must_use]
-    pub const fn init_with_costs(costs: Costs<C>) -> Economy<C, 2> {
-        Economy::<C, 2> {
-            costs: Some(costs),
-            roi: None,
+    pub const fn init_with_roi(roi: Rois<R>) -> Self {
+        Self {
+            costs: None,
+            roi: Some(roi),
         }
     }
+}
 
-    /// Creates a new [`Economy`] instance initialized with [`Rois`] data.
+impl<const C: usize, const R: usize> Economy<C, R> {
+    /// Creates an empty [`Economy`] instance.
     #[must_use]
-    pub const fn init_with_roi(roi: Rois<R>) -> Economy<2, R> {
-        Economy::<2, R> {
+    pub const fn empty() -> Self {
+        Self {
             costs: None,
-            roi: Some(roi),
+            roi: None,
         }
     }
 
@@ -74,4 +87,138 @@ impl<const C: usize, const R: usize> Economy<C, R> {
     pub const fn is_empty(&self) -> bool {
         self.costs.is_none() && self.roi.is_none()
     }
+
+    /// Validates the consistency of [`Economy`] data.
+    ///
+    /// # Errors
+    ///
+    /// The first [`EconomyViolation`] is returned when:
+    ///
+    /// - a [`Cost`] is negative
+    /// - two [`Cost`]s have the same [`CostTimespan`] but different amounts
+    /// - a [`Roi`] is not in the range from 1 to 30 years
+    /// - two [`Roi`]s have the same energy class but different years
+    pub fn validate(&self) -> core::result::Result<(), EconomyViolation> {
+        if let Some(costs) = &self.costs {
+            let mut iter = costs.iter();
+            while let Some(cost) = iter.next() {
+                if cost.usd_currency < 0 {
+                    return Err(EconomyViolation::NegativeCost(cost.timespan));
+                }
+                if iter.clone().any(|other| other.timespan == cost.timespan) {
+                    return Err(EconomyViolation::DuplicateCost(cost.timespan));
+                }
+            }
+        }
+
+        if let Some(rois) = &self.roi {
+            let mut iter = rois.iter();
+            while let Some(roi) = iter.next() {
+                if !ROI_YEARS.contains(&roi.years) {
+                    return Err(EconomyViolation::RoiOutOfRange(roi.energy_class));
+                }
+                if iter
+                    .clone()
+                    .any(|other| other.energy_class == roi.energy_class)
+                {
+                    return Err(EconomyViolation::DuplicateRoi(roi.energy_class));
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// A violation of the consistency of [`Economy`] data.
+///
+/// It carries the [`CostTimespan`] or the [`EnergyClass`] which violates the
+/// consistency.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EconomyViolation {
+    /// A [`Cost`] is negative.
+    NegativeCost(CostTimespan),
+    /// Two [`Cost`]s have the same [`CostTimespan`].
+    DuplicateCost(CostTimespan),
+    /// A [`Roi`] is out of range.
+    RoiOutOfRange(EnergyClass),
+    /// Two [`Roi`]s have the same [`EnergyClass`].
+    DuplicateRoi(EnergyClass),
+}
+
+impl EconomyViolation {
+    /// Returns the information about the violation.
+    #[must_use]
+    pub const fn info(self) -> &'static str {
+        match self {
+            Self::NegativeCost(_) => "Negative cost",
+            Self::DuplicateCost(_) => "Different costs for the same timespan",
+            Self::RoiOutOfRange(_) => "ROI years out of range",
+            Self::DuplicateRoi(_) => "Different ROIs for the same energy class",
+        }
+    }
+}
+
+impl From<EconomyViolation> for Error {
+    fn from(violation: EconomyViolation) -> Self {
+        Self::new(ErrorKind::Economy, violation.info())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::energy::EnergyClass;
+    use crate::error::{Error, ErrorKind};
+
+    use super::{Cost, CostTimespan, Costs, Economy, EconomyViolation, Roi, Rois};
+
+    #[test]
+    fn test_validate() {
+        let economy = Economy::<2, 2>::empty()
+            .costs(Costs::<4>::init_with_elements(&[
+                Cost::new(10, CostTimespan::Week),
+                Cost::new(40, CostTimespan::Month),
+            ]))
+            .rois(Rois::<2>::init(Roi::new(5, EnergyClass::A)));
+        assert!(economy.validate().is_ok());
+        assert!(Economy::<2, 2>::empty().validate().is_ok());
+
+        let negative = economy
+            .clone()
+            .costs(Costs::<2>::init(Cost::new(-1, CostTimespan::Year)));
+        let violation = negative.validate().unwrap_err();
+        assert_eq!(
+            violation,
+            EconomyViolation::NegativeCost(CostTimespan::Year)
+        );
+        assert_eq!(Error::from(violation).kind(), ErrorKind::Economy);
+
+        let contradiction = economy.clone().costs(Costs::<2>::init_with_elements(&[
+            Cost::new(10, CostTimespan::Week),
+            Cost::new(12, CostTimespan::Week),
+        ]));
+        assert_eq!(
+            contradiction.validate(),
+            Err(EconomyViolation::DuplicateCost(CostTimespan::Week))
+        );
+
+        // The constructor clamps years, so a struct literal is needed.
+        let out_of_range = economy.clone().rois(Rois::<2>::init(Roi {
+            years: 0,
+            energy_class: EnergyClass::A,
+        }));
+        assert_eq!(
+            out_of_range.validate(),
+            Err(EconomyViolation::RoiOutOfRange(EnergyClass::A))
+        );
+
+        let contradiction = economy.rois(Rois::<2>::init_with_elements(&[
+            Roi::new(5, EnergyClass::B),
+            Roi::new(6, EnergyClass::B),
+        ]));
+        assert_eq!(
+            contradiction.validate(),
+            Err(EconomyViolation::DuplicateRoi(EnergyClass::B))
+        );
+    }
 }
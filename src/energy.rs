@@ -1,9 +1,13 @@
 use serde::{Deserialize, Serialize};
 
 use crate::collections::OutputSet;
+use crate::error::{Error, ErrorKind};
 
 pub use tosca::energy::{CarbonFootprint, EnergyClass, EnergyEfficiency, WaterUseEfficiency};
 
+// Range of valid decimal percentages.
+const PERCENTAGE_RANGE: core::ops::RangeInclusive<i8> = -100..=100;
+
 /// A collection of [`EnergyEfficiency`]s.
 pub type EnergyEfficiencies<const E: usize> = OutputSet<EnergyEfficiency, E>;
 
@@ -27,45 +31,42 @@ pub struct Energy<const E: usize, const CF: usize> {
     pub water_use_efficiency: Option<WaterUseEfficiency>,
 }
 
-impl<const E: usize, const CF: usize> Energy<E, CF> {
-    /// Creates an empty [`Energy`] instance.
-    #[must_use]
-    pub const fn empty() -> Self {
-        Self {
-            energy_efficiencies: None,
-            carbon_footprints: None,
-            water_use_efficiency: None,
-        }
-    }
-
+impl<const E: usize> Energy<E, 0> {
     /// Creates a new [`Energy`] instance initialized with
     /// [`EnergyEfficiencies`] data.
+    ///
+    /// [`CarbonFootprints`] have zero capacity until they are added.
     #[must_use]
-    pub const fn init_with_energy_efficiencies(
-        energy_efficiencies: EnergyEfficiencies<E>,
-    ) -> Energy<E, 2> {
-        Energy::<E, 2> {
+    pub const fn init_with_energy_efficiencies(energy_efficiencies: EnergyEfficiencies<E>) -> Self {
+        Self {
             energy_efficiencies: Some(energy_efficiencies),
             carbon_footprints: None,
             water_use_efficiency: None,
         }
     }
+}
 
+impl<const CF: usize> Energy<0, CF> {
     /// Creates a new [`Energy`] instance initialized with
     /// [`CarbonFootprints`] data.
+    ///
+    /// [`EnergyEfficiencies`] have zero capacity until they are added.
     #[must_use]
-    pub const fn init_with_carbon_footprints(
-        carbon_footprints: CarbonFootprints<CF>,
-    ) -> Energy<2, CF> {
-        Energy::<2, CF> {
+    pub const fn init_with_carbon_footprints(carbon_footprints: CarbonFootprints<CF>) -> Self {
+        Self {
             energy_efficiencies: None,
             carbon_footprints: Some(carbon_footprints),
             water_use_efficiency: None,
         }
     }
+}
 
+impl Energy<0, 0> {
     /// Creates a new [`Energy`] instance initialized with
     /// [`WaterUseEfficiency`] data.
+    ///
+    /// [`EnergyEfficiencies`] and [`CarbonFootprints`] have zero capacity
+    /// until they are added.
     #[must_use]
     pub const fn init_with_water_use_efficiency(water_use_efficiency: WaterUseEfficiency) -> Self {
         Self {
@@ -74,6 +75,18 @@ impl<const E: usize, const CF: usize> Energy<E, CF> {
             water_use_efficiency: Some(water_use_efficiency),
         }
     }
+}
+
+impl<const E: usize, const CF: usize> Energy<E, CF> {
+    /// Creates an empty [`Energy`] instance.
+    #[must_use]
+    pub const fn empty() -> Self {
+        Self {
+            energy_efficiencies: None,
+            carbon_footprints: None,
+            water_use_efficiency: None,
+        }
+    }
 
     /// Adds [`EnergyEfficiencies`] data.
     #[must_use]
@@ -117,4 +130,185 @@ impl<const E: usize, const CF: usize> Energy<E, CF> {
             && self.carbon_footprints.is_none()
             && self.water_use_efficiency.is_none()
     }
+
+    /// Validates the consistency of [`Energy`] data.
+    ///
+    /// # Errors
+    ///
+    /// The first [`EnergyViolation`] is returned when:
+    ///
+    /// - two [`EnergyEfficiency`]s or two [`CarbonFootprint`]s have the same
+    ///   [`EnergyClass`] but different percentages
+    /// - a percentage is not in the range from -100 to 100
+    /// - a [`WaterUseEfficiency`] value is negative or not finite
+    pub fn validate(&self) -> core::result::Result<(), EnergyViolation> {
+        if let Some(energy_efficiencies) = &self.energy_efficiencies {
+            let mut iter = energy_efficiencies.iter();
+            while let Some(efficiency) = iter.next() {
+                if !PERCENTAGE_RANGE.contains(&efficiency.percentage) {
+                    return Err(EnergyViolation::EnergyEfficiencyOutOfRange(
+                        efficiency.energy_class,
+                    ));
+                }
+                if iter
+                    .clone()
+                    .any(|other| other.energy_class == efficiency.energy_class)
+                {
+                    return Err(EnergyViolation::DuplicateEnergyEfficiency(
+                        efficiency.energy_class,
+                    ));
+                }
+            }
+        }
+
+        if let Some(carbon_footprints) = &self.carbon_footprints {
+            let mut iter = carbon_footprints.iter();
+            while let Some(footprint) = iter.next() {
+                if !PERCENTAGE_RANGE.contains(&footprint.percentage) {
+                    return Err(EnergyViolation::CarbonFootprintOutOfRange(
+                        footprint.energy_class,
+                    ));
+                }
+                if iter
+                    .clone()
+                    .any(|other| other.energy_class == footprint.energy_class)
+                {
+                    return Err(EnergyViolation::DuplicateCarbonFootprint(
+                        footprint.energy_class,
+                    ));
+                }
+            }
+        }
+
+        if let Some(water_use_efficiency) = &self.water_use_efficiency
+            && let Some((field, _)) = [
+                (WaterUseField::Gpp, water_use_efficiency.gpp),
+                (
+                    WaterUseField::PenmanMonteithEquation,
+                    water_use_efficiency.penman_monteith_equation,
+                ),
+                (WaterUseField::Wer, water_use_efficiency.wer),
+            ]
+            .into_iter()
+            .find(|(_, value)| value.is_some_and(|value| !value.is_finite() || value < 0.))
+        {
+            return Err(EnergyViolation::WaterUseEfficiency(field));
+        }
+
+        Ok(())
+    }
+}
+
+/// A value of a [`WaterUseEfficiency`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WaterUseField {
+    /// Gross primary productivity.
+    Gpp,
+    /// Penman-Monteith equation.
+    PenmanMonteithEquation,
+    /// Water equivalent ratio.
+    Wer,
+}
+
+/// A violation of the consistency of [`Energy`] data.
+///
+/// It carries the [`EnergyClass`] or the [`WaterUseField`] which violates
+/// the consistency.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EnergyViolation {
+    /// An [`EnergyEfficiency`] percentage is out of range.
+    EnergyEfficiencyOutOfRange(EnergyClass),
+    /// Two [`EnergyEfficiency`]s have the same [`EnergyClass`].
+    DuplicateEnergyEfficiency(EnergyClass),
+    /// A [`CarbonFootprint`] percentage is out of range.
+    CarbonFootprintOutOfRange(EnergyClass),
+    /// Two [`CarbonFootprint`]s have the same [`EnergyClass`].
+    DuplicateCarbonFootprint(EnergyClass),
+    /// A [`WaterUseEfficiency`] value is negative or not finite.
+    WaterUseEfficiency(WaterUseField),
+}
+
+impl EnergyViolation {
+    /// Returns the information about the violation.
+    #[must_use]
+    pub const fn info(self) -> &'static str {
+        match self {
+            Self::EnergyEfficiencyOutOfRange(_) => "Energy efficiency percentage out of range",
+            Self::DuplicateEnergyEfficiency(_) => {
+                "Different energy efficiencies for the same energy class"
+            }
+            Self::CarbonFootprintOutOfRange(_) => "Carbon footprint percentage out of range",
+            Self::DuplicateCarbonFootprint(_) => {
+                "Different carbon footprints for the same energy class"
+            }
+            Self::WaterUseEfficiency(_) => "Water-use efficiency value negative or not finite",
+        }
+    }
+}
+
+impl From<EnergyViolation> for Error {
+    fn from(violation: EnergyViolation) -> Self {
+        Self::new(ErrorKind::Energy, violation.info())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::error::{Error, ErrorKind};
+
+    use super::{
+        CarbonFootprint, CarbonFootprints, Energy, EnergyClass, EnergyEfficiencies,
+        EnergyEfficiency, EnergyViolation, WaterUseEfficiency, WaterUseField,
+    };
+
+    #[test]
+    fn test_validate() {
+        let energy = Energy::<2, 2>::empty()
+            .energy_efficiencies(EnergyEfficiencies::<4>::init_with_elements(&[
+                EnergyEfficiency::new(-20, EnergyClass::A),
+                EnergyEfficiency::new(10, EnergyClass::C),
+            ]))
+            .carbon_footprints(CarbonFootprints::<2>::init(CarbonFootprint::new(
+                5,
+                EnergyClass::A,
+            )));
+        assert!(energy.validate().is_ok());
+        assert!(Energy::<2, 2>::empty().validate().is_ok());
+
+        let contradiction =
+            energy
+                .clone()
+                .energy_efficiencies(EnergyEfficiencies::<4>::init_with_elements(&[
+                    EnergyEfficiency::new(-20, EnergyClass::A),
+                    EnergyEfficiency::new(10, EnergyClass::A),
+                ]));
+        let violation = contradiction.validate().unwrap_err();
+        assert_eq!(
+            violation,
+            EnergyViolation::DuplicateEnergyEfficiency(EnergyClass::A)
+        );
+        assert_eq!(Error::from(violation).kind(), ErrorKind::Energy);
+
+        // Constructors clamp percentages, so a struct literal is needed.
+        let out_of_range = energy
+            .clone()
+            .carbon_footprints(CarbonFootprints::<2>::init(CarbonFootprint {
+                percentage: 101,
+                energy_class: EnergyClass::B,
+            }));
+        assert_eq!(
+            out_of_range.validate(),
+            Err(EnergyViolation::CarbonFootprintOutOfRange(EnergyClass::B))
+        );
+
+        let negative = energy.water_use_efficiency(WaterUseEfficiency {
+            gpp: Some(1.),
+            penman_monteith_equation: None,
+            wer: Some(-1.),
+        });
+        assert_eq!(
+            negative.validate(),
+            Err(EnergyViolation::WaterUseEfficiency(WaterUseField::Wer))
+        );
+    }
 }
@@ -1,14 +1,65 @@
 /// All possible error kinds.
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum ErrorKind {
     /// Error creating a fixed-size text.
     FixedText,
+    /// Error handling a route input parameter.
+    Parameter,
+    /// Error validating a route.
+    Route,
+    /// Error adding an already existing route.
+    DuplicateRoute,
+    /// Error adding an element to a full fixed-capacity collection.
+    Capacity,
+    /// Error parsing a version.
+    Version,
+    /// Error validating energy information.
+    Energy,
+    /// Error validating economy information.
+    Economy,
+    /// Error authenticating a request.
+    Authentication,
+    /// Error authorizing a route invocation.
+    Authorization,
+    /// Error invoking a route beyond its rate limit.
+    RateLimit,
 }
 
 impl ErrorKind {
+    /// Returns the numeric code of the [`ErrorKind`].
+    ///
+    /// Codes are stable, so a controller can rely on them to identify
+    /// an error.
+    #[must_use]
+    pub const fn code(self) -> u16 {
+        match self {
+            Self::FixedText => 1,
+            Self::Parameter => 2,
+            Self::Route => 3,
+            Self::DuplicateRoute => 4,
+            Self::Capacity => 5,
+            Self::Version => 6,
+            Self::Energy => 7,
+            Self::Economy => 8,
+            Self::Authentication => 9,
+            Self::Authorization => 10,
+            Self::RateLimit => 11,
+        }
+    }
+
     pub(crate) const fn description(self) -> &'static str {
         match self {
             Self::FixedText => "Fixed-size text",
+            Self::Parameter => "Route parameter",
+            Self::Route => "Route",
+            Self::DuplicateRoute => "Duplicate route",
+            Self::Capacity => "Fixed-capacity collection",
+            Self::Version => "Version",
+            Self::Energy => "Energy information",
+            Self::Economy => "Economy information",
+            Self::Authentication => "Request authentication",
+            Self::Authorization => "Route authorization",
+            Self::RateLimit => "Route rate limit",
         }
     }
 }
@@ -27,9 +78,21 @@ pub struct Error {
 }
 
 impl Error {
-    pub(crate) fn new(kind: ErrorKind, info: &'static str) -> Self {
+    pub(crate) const fn new(kind: ErrorKind, info: &'static str) -> Self {
         Self { kind, info }
     }
+
+    /// Returns the [`ErrorKind`].
+    #[must_use]
+    pub const fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+
+    /// Returns the information about the error.
+    #[must_use]
+    pub const fn info(&self) -> &'static str {
+        self.info
+    }
 }
 
 impl core::fmt::Display for Error {
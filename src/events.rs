@@ -0,0 +1,469 @@
+use heapless::Deque;
+
+use serde::{Deserialize, Serialize};
+
+use crate::collections::{Backing, Hashed};
+use crate::error::Result;
+use crate::hazards::{Category, Hazard, Hazards};
+use crate::string::String;
+
+/// Severity of an [`Event`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum Severity {
+    /// Informative event.
+    Info,
+    /// Event which might require an action.
+    Warning,
+    /// Event which requires an immediate action.
+    Critical,
+}
+
+impl Severity {
+    /// Returns the [`Severity`] of an event triggered by a [`Hazard`].
+    ///
+    /// Safety hazards are critical, while financial and privacy hazards
+    /// are warnings.
+    #[must_use]
+    pub const fn from_hazard(hazard: Hazard) -> Self {
+        match hazard.category() {
+            Category::Safety => Self::Critical,
+            Category::Financial | Category::Privacy => Self::Warning,
+        }
+    }
+}
+
+/// Value of an [`Event`].
+///
+/// It is serialized as the bare value, as the value of a
+/// [`tosca::events::Event`].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum EventValue {
+    /// A [`bool`] value.
+    Bool(bool),
+    /// An [`u8`] value.
+    U8(u8),
+    /// An [`i32`] value.
+    I32(i32),
+    /// A [`f32`] value.
+    F32(f32),
+    /// A [`f64`] value.
+    F64(f64),
+}
+
+macro_rules! from_event_value {
+    ($ty:ty, $variant:ident) => {
+        impl From<$ty> for EventValue {
+            fn from(value: $ty) -> Self {
+                Self::$variant(value)
+            }
+        }
+    };
+}
+
+from_event_value!(bool, Bool);
+from_event_value!(u8, U8);
+from_event_value!(i32, I32);
+from_event_value!(f32, F32);
+from_event_value!(f64, F64);
+
+/// An event occurred on a device.
+///
+/// An event reports the [`EventValue`] of a named device quantity, for
+/// example the measured temperature. It might be triggered by one or more
+/// [`Hazard`]s and carry a short payload of `N` bytes.
+///
+/// It is serialized as a [`tosca::events::Event`], whose value type is the
+/// one of its [`EventValue`]. The identifier, the timestamp, the route, the
+/// [`Hazards`], the [`Severity`] and the payload are extension fields, which
+/// a `tosca` controller ignores.
+///
+/// The [`Backing`] of its [`Hazards`] is [`Hashed`] by default.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(bound = "")]
+pub struct Event<const H: usize, const N: usize, B: Backing = Hashed> {
+    /// Event name.
+    name: &'static str,
+    /// Event description.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    description: Option<&'static str>,
+    /// Event value.
+    value: EventValue,
+    /// Event identifier, assigned by an [`EventQueue`].
+    id: u32,
+    /// Event timestamp, expressed in seconds.
+    timestamp: u64,
+    /// Route which raised the event.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    route: Option<&'static str>,
+    /// Hazards which triggered the event.
+    #[serde(skip_serializing_if = "Hazards::is_empty")]
+    hazards: Hazards<H, B>,
+    /// Severity.
+    severity: Severity,
+    /// Payload.
+    #[serde(skip_serializing_if = "String::is_empty")]
+    payload: String<N>,
+}
+
+impl<const H: usize, const N: usize, B: Backing> Event<H, N, B> {
+    /// Creates an [`Event`] with the value of a named quantity and a
+    /// [`Severity`], occurred at the given timestamp.
+    #[must_use]
+    #[inline]
+    pub fn new(
+        name: &'static str,
+        value: impl Into<EventValue>,
+        timestamp: u64,
+        severity: Severity,
+    ) -> Self {
+        Self {
+            name,
+            description: None,
+            value: value.into(),
+            id: 0,
+            timestamp,
+            route: None,
+            hazards: Hazards::new(),
+            severity,
+            payload: String::empty(),
+        }
+    }
+
+    /// Creates an [`Event`] with the value of a named quantity, triggered by
+    /// a [`Hazard`] occurred at the given timestamp.
+    ///
+    /// The [`Severity`] is the one returned by [`Severity::from_hazard`].
+    ///
+    /// # Errors
+    ///
+    /// If the [`Hazards`] capacity is zero, an error is returned.
+    #[inline]
+    pub fn hazard(
+        name: &'static str,
+        value: impl Into<EventValue>,
+        timestamp: u64,
+        hazard: Hazard,
+    ) -> Result<Self> {
+        Self::new(name, value, timestamp, Severity::from_hazard(hazard)).with_hazard(hazard)
+    }
+
+    /// Sets the event description.
+    #[must_use]
+    pub const fn description(mut self, description: &'static str) -> Self {
+        self.description = Some(description);
+        self
+    }
+
+    /// Sets the route which raised the [`Event`].
+    #[must_use]
+    pub const fn with_route(mut self, route: &'static str) -> Self {
+        self.route = Some(route);
+        self
+    }
+
+    /// Adds a [`Hazard`] which triggered the [`Event`].
+    ///
+    /// # Errors
+    ///
+    /// If the [`Hazards`] capacity has been reached, an error is returned.
+    #[inline]
+    pub fn with_hazard(mut self, hazard: Hazard) -> Result<Self> {
+        self.hazards.try_add(hazard)?;
+        Ok(self)
+    }
+
+    /// Sets the payload.
+    ///
+    /// A payload greater than `N` bytes is truncated and terminated with an
+    /// [`ELLIPSIS`](crate::string::ELLIPSIS).
+    #[must_use]
+    #[inline]
+    pub fn with_payload(mut self, payload: &str) -> Self {
+        self.payload = String::truncated_with_ellipsis(payload);
+        self
+    }
+
+    /// Returns the event name.
+    #[must_use]
+    pub const fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Returns the [`EventValue`].
+    #[must_use]
+    pub const fn value(&self) -> EventValue {
+        self.value
+    }
+
+    /// Returns the identifier assigned by an [`EventQueue`].
+    #[must_use]
+    pub const fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Returns the timestamp, expressed in seconds.
+    #[must_use]
+    pub const fn timestamp(&self) -> u64 {
+        self.timestamp
+    }
+
+    /// Returns the route which raised the [`Event`].
+    #[must_use]
+    pub const fn route(&self) -> Option<&'static str> {
+        self.route
+    }
+
+    /// Returns the [`Hazards`] which triggered the [`Event`].
+    #[must_use]
+    pub const fn hazards(&self) -> &Hazards<H, B> {
+        &self.hazards
+    }
+
+    /// Returns the [`Severity`].
+    #[must_use]
+    pub const fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    /// Returns the payload.
+    #[must_use]
+    pub fn payload(&self) -> &str {
+        self.payload.as_str()
+    }
+}
+
+/// A fixed-capacity queue of outbound [`Event`]s.
+///
+/// The queue contains at most `Q` events. When it is full, the oldest event
+/// is discarded to make room for a new one, and the number of discarded
+/// events is reported, so a controller knows that some events are lost.
+///
+/// Events are delivered either by polling, serializing the queue and then
+/// acknowledging the received events, or by pushing them one at a time
+/// through [`EventQueue::pop`].
+#[derive(Debug, Clone, Serialize)]
+#[serde(bound = "")]
+pub struct EventQueue<const H: usize, const N: usize, const Q: usize, B: Backing = Hashed> {
+    /// Pending events, from the oldest to the newest.
+    events: Deque<Event<H, N, B>, Q>,
+    /// Number of discarded events.
+    dropped: u32,
+    // Identifier of the next event.
+    #[serde(skip)]
+    next_id: u32,
+}
+
+impl<const H: usize, const N: usize, const Q: usize, B: Backing> Default
+    for EventQueue<H, N, Q, B>
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const H: usize, const N: usize, const Q: usize, B: Backing> EventQueue<H, N, Q, B> {
+    /// Creates an empty [`EventQueue`].
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            events: Deque::new(),
+            dropped: 0,
+            next_id: 0,
+        }
+    }
+
+    /// Raises an [`Event`], assigning it a new identifier which is
+    /// returned.
+    ///
+    /// If the queue is full, the oldest event is discarded.
+    pub fn raise(&mut self, mut event: Event<H, N, B>) -> u32 {
+        event.id = self.next_id;
+        self.next_id = self.next_id.wrapping_add(1);
+
+        if self.events.is_full() && self.events.pop_front().is_some() {
+            self.dropped = self.dropped.saturating_add(1);
+        }
+        // A zero-capacity queue discards all events.
+        if self.events.push_back(event).is_err() {
+            self.dropped = self.dropped.saturating_add(1);
+        }
+
+        self.next_id.wrapping_sub(1)
+    }
+
+    /// Removes and returns the oldest [`Event`].
+    pub fn pop(&mut self) -> Option<Event<H, N, B>> {
+        self.events.pop_front()
+    }
+
+    /// Returns the oldest [`Event`] without removing it.
+    #[must_use]
+    pub fn peek(&self) -> Option<&Event<H, N, B>> {
+        self.events.front()
+    }
+
+    /// Removes all [`Event`]s up to and including the one with the given
+    /// identifier.
+    ///
+    /// It is invoked once a controller has received the polled events.
+    pub fn acknowledge(&mut self, id: u32) {
+        while self
+            .events
+            .front()
+            .is_some_and(|event| event.id.wrapping_sub(id).cast_signed() <= 0)
+        {
+            self.events.pop_front();
+        }
+    }
+
+    /// Returns an iterator over the pending [`Event`]s, from the oldest to
+    /// the newest.
+    pub fn iter(&self) -> impl Iterator<Item = &Event<H, N, B>> {
+        self.events.iter()
+    }
+
+    /// Returns the number of pending [`Event`]s.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    /// Checks whether there are no pending [`Event`]s.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    /// Returns the number of discarded [`Event`]s.
+    #[must_use]
+    pub const fn dropped(&self) -> u32 {
+        self.dropped
+    }
+
+    /// Removes all pending [`Event`]s and resets the number of discarded
+    /// ones.
+    ///
+    /// Identifiers keep increasing, so they are never reused.
+    pub fn clear(&mut self) {
+        self.events.clear();
+        self.dropped = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::collections::Linear;
+    use crate::error::ErrorKind;
+    use crate::hazards::Hazard;
+    use crate::serialize;
+
+    use super::{Event, EventQueue, EventValue, Severity};
+
+    #[test]
+    fn test_event() {
+        let event = Event::<2, 16>::hazard("temperature", 95.5f32, 120, Hazard::FireHazard)
+            .unwrap()
+            .description("Heater temperature")
+            .with_route("/heater")
+            .with_payload("Over temperature");
+
+        assert_eq!(event.name(), "temperature");
+        assert_eq!(event.value(), EventValue::F32(95.5));
+        assert_eq!(event.severity(), Severity::Critical);
+        assert_eq!(event.route(), Some("/heater"));
+        assert!(event.hazards().contains(&Hazard::FireHazard));
+
+        assert_eq!(
+            serialize(&event),
+            json!({
+                "name": "temperature",
+                "description": "Heater temperature",
+                "value": 95.5,
+                "id": 0,
+                "timestamp": 120,
+                "route": "/heater",
+                "hazards": ["FireHazard"],
+                "severity": "Critical",
+                "payload": "Over temperature"
+            })
+        );
+
+        assert_eq!(
+            serialize(Event::<2, 16>::new("door", true, 5, Severity::Info)),
+            json!({
+                "name": "door",
+                "value": true,
+                "id": 0,
+                "timestamp": 5,
+                "severity": "Info"
+            })
+        );
+        assert_eq!(Severity::from_hazard(Hazard::SpendMoney), Severity::Warning);
+
+        // Hazards exceeding the capacity are reported.
+        assert_eq!(
+            Event::<1, 0, Linear>::hazard("temperature", 95u8, 5, Hazard::FireHazard)
+                .unwrap()
+                .with_hazard(Hazard::Explosion)
+                .unwrap_err()
+                .kind(),
+            ErrorKind::Capacity
+        );
+    }
+
+    #[test]
+    fn test_event_queue() {
+        let mut queue = EventQueue::<2, 8, 2>::new();
+        assert!(queue.is_empty());
+
+        queue.raise(Event::new("door", true, 1, Severity::Info));
+        let second = queue.raise(Event::hazard("leak", true, 2, Hazard::WaterFlooding).unwrap());
+        let third = queue
+            .raise(Event::new("battery", 8u8, 3, Severity::Warning).with_payload("Low battery"));
+
+        // The oldest event has been discarded.
+        assert_eq!(queue.len(), 2);
+        assert_eq!(queue.dropped(), 1);
+        assert_eq!(queue.peek().map(Event::id), Some(second));
+        assert_eq!(queue.iter().last().unwrap().payload(), "Low b...");
+
+        assert_eq!(
+            serialize(&queue),
+            json!({
+                "events": [
+                    {
+                        "name": "leak",
+                        "value": true,
+                        "id": 1,
+                        "timestamp": 2,
+                        "hazards": ["WaterFlooding"],
+                        "severity": "Critical"
+                    },
+                    {
+                        "name": "battery",
+                        "value": 8,
+                        "id": 2,
+                        "timestamp": 3,
+                        "severity": "Warning",
+                        "payload": "Low b..."
+                    }
+                ],
+                "dropped": 1
+            })
+        );
+
+        queue.acknowledge(second);
+        assert_eq!(queue.pop().map(|event| event.id()), Some(third));
+        assert!(queue.pop().is_none());
+
+        queue.clear();
+        assert_eq!(queue.dropped(), 0);
+        assert_eq!(
+            queue.raise(Event::new("door", false, 4, Severity::Info)),
+            third + 1
+        );
+    }
+}
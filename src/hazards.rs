@@ -1,9 +1,11 @@
-use crate::collections::OutputSet;
+use crate::collections::{Hashed, OutputSet};
 
 pub use tosca::hazards::{ALL_HAZARDS, Category, Hazard, HazardData};
 
 /// A collection of [`Hazard`]s.
 ///
-/// **For alignment reasons, it accepts only a power of two
-/// as number of elements.**
-pub type Hazards<const N: usize> = OutputSet<Hazard, N>;
+/// **For alignment reasons, with the default [`Hashed`] backing it accepts
+/// only a power of two as number of elements.** With a
+/// [`Linear`](crate::collections::Linear) backing, it accepts any number of
+/// elements, zero included.
+pub type Hazards<const N: usize, B = Hashed> = OutputSet<Hazard, N, B>;
@@ -0,0 +1,399 @@
+use crate::collections::Backing;
+use crate::device::DeviceData;
+use crate::error::Result;
+use crate::response::ResponseKind;
+use crate::route::{
+    RestKind, Route, RouteConfig, check_route, duplicate_route, full_route_configs,
+};
+
+/// Route through which a controller fetches the
+/// [`InfoResponse`](crate::response::InfoResponse) of a device.
+pub const INFO_ROUTE: &str = "/info";
+
+/// Route through which a controller fetches the
+/// [`HealthResponse`](crate::response::HealthResponse) of a device.
+pub const HEALTH_ROUTE: &str = "/health";
+
+/// Route through which a controller fetches the
+/// [`HazardsResponse`](crate::response::HazardsResponse) of a device.
+pub const HAZARDS_ROUTE: &str = "/hazards";
+
+/// A built-in introspection route.
+///
+/// All built-in routes are invoked through a REST `GET` API.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BuiltinRoute {
+    /// Device description, served on the device main route as
+    /// [`DeviceData`].
+    Description,
+    /// Economy and energy information, served on [`INFO_ROUTE`] as an
+    /// [`InfoResponse`](crate::response::InfoResponse).
+    Info,
+    /// Health status and uptime, served on [`HEALTH_ROUTE`] as a
+    /// [`HealthResponse`](crate::response::HealthResponse).
+    Health,
+    /// Hazards summary, served on [`HAZARDS_ROUTE`] as a
+    /// [`HazardsResponse`](crate::response::HazardsResponse).
+    Hazards,
+}
+
+impl BuiltinRoute {
+    /// All built-in routes.
+    pub const ALL: [Self; 4] = [Self::Description, Self::Info, Self::Health, Self::Hazards];
+
+    /// Returns the path of a built-in route for a device with the given
+    /// main route.
+    #[must_use]
+    pub const fn path(self, main_route: &'static str) -> &'static str {
+        match self {
+            Self::Description => main_route,
+            Self::Info => INFO_ROUTE,
+            Self::Health => HEALTH_ROUTE,
+            Self::Hazards => HAZARDS_ROUTE,
+        }
+    }
+
+    /// Returns the [`ResponseKind`] of a built-in route.
+    #[must_use]
+    pub const fn response_kind(self) -> ResponseKind {
+        match self {
+            Self::Info => ResponseKind::Info,
+            Self::Description | Self::Health | Self::Hazards => ResponseKind::Serial,
+        }
+    }
+
+    /// Creates the `GET` [`Route`] of a built-in route for a device with the
+    /// given main route.
+    ///
+    /// Its capacities are inferred from the collection of routes it is
+    /// added to.
+    #[must_use]
+    pub const fn route<const H: usize, const P: usize, B: Backing>(
+        self,
+        main_route: &'static str,
+    ) -> Route<H, P, B> {
+        Route::init(RestKind::Get, self.path(main_route)).description(self.description())
+    }
+
+    /// Creates the [`RouteConfig`] of a built-in route for a device with the
+    /// given main route.
+    #[must_use]
+    pub fn route_config<const H: usize, const P: usize, B: Backing>(
+        self,
+        main_route: &'static str,
+    ) -> RouteConfig<H, P, B> {
+        self.route(main_route)
+            .serialize_data()
+            .with_response_kind(self.response_kind())
+    }
+
+    const fn description(self) -> &'static str {
+        match self {
+            Self::Description => "Device description",
+            Self::Info => "Economy and energy information",
+            Self::Health => "Health status and uptime",
+            Self::Hazards => "Summary of the hazards of all routes",
+        }
+    }
+}
+
+/// A set of opt-in built-in introspection routes.
+///
+/// The enabled [`BuiltinRoute`]s are registered in the route configurations
+/// of a [`DeviceData`], so a controller discovers them as any other route.
+/// When a request arrives, [`Introspection::resolve`] tells whether it is
+/// addressed to a built-in route.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Introspection {
+    // Enabled built-in routes, one bit for each route.
+    routes: u8,
+}
+
+impl Introspection {
+    /// Creates an [`Introspection`] without built-in routes.
+    #[must_use]
+    pub const fn none() -> Self {
+        Self { routes: 0 }
+    }
+
+    /// Creates an [`Introspection`] with all built-in routes.
+    #[must_use]
+    pub const fn all() -> Self {
+        Self::none()
+            .with_description()
+            .with_info()
+            .with_health()
+            .with_hazards()
+    }
+
+    /// Enables the [`BuiltinRoute::Description`] route.
+    #[must_use]
+    pub const fn with_description(self) -> Self {
+        self.enable(BuiltinRoute::Description)
+    }
+
+    /// Enables the [`BuiltinRoute::Info`] route.
+    #[must_use]
+    pub const fn with_info(self) -> Self {
+        self.enable(BuiltinRoute::Info)
+    }
+
+    /// Enables the [`BuiltinRoute::Health`] route.
+    #[must_use]
+    pub const fn with_health(self) -> Self {
+        self.enable(BuiltinRoute::Health)
+    }
+
+    /// Enables the [`BuiltinRoute::Hazards`] route.
+    #[must_use]
+    pub const fn with_hazards(self) -> Self {
+        self.enable(BuiltinRoute::Hazards)
+    }
+
+    /// Checks whether a [`BuiltinRoute`] is enabled.
+    #[must_use]
+    pub const fn is_enabled(&self, route: BuiltinRoute) -> bool {
+        self.routes & (1 << route as u8) != 0
+    }
+
+    /// Returns an iterator over the enabled [`BuiltinRoute`]s.
+    pub fn routes(self) -> impl Iterator<Item = BuiltinRoute> {
+        BuiltinRoute::ALL
+            .into_iter()
+            .filter(move |route| self.is_enabled(*route))
+    }
+
+    /// Registers the enabled [`BuiltinRoute`]s in the route configurations
+    /// of a [`DeviceData`].
+    ///
+    /// All built-in routes are checked as routes added through a
+    /// [`RouteConfigsBuilder`](crate::route::RouteConfigsBuilder) before
+    /// registering any of them, so the device is left unchanged on error.
+    ///
+    /// # Errors
+    ///
+    /// If the path of an enabled built-in route is not valid, if the device
+    /// already has a `GET` route with that path, if two enabled built-in
+    /// routes have the same path, or if the route configurations capacity
+    /// is not enough to contain all of them, an error is returned.
+    pub fn register<const H: usize, const I: usize, const N: usize, const S: usize, B: Backing>(
+        self,
+        device: &mut DeviceData<H, I, N, S, B>,
+    ) -> Result<()> {
+        let main_route = device.main_route;
+        for (index, route) in self.routes().enumerate() {
+            let path = route.path(main_route);
+            check_route(&device.route_configs, path, RestKind::Get)?;
+            if self
+                .routes()
+                .take(index)
+                .any(|other| other.path(main_route) == path)
+            {
+                return Err(duplicate_route());
+            }
+        }
+
+        if device.route_configs.len() + self.routes().count() > N {
+            return Err(full_route_configs());
+        }
+
+        for route in self.routes() {
+            device.route_configs.add(route.route_config(main_route));
+        }
+        Ok(())
+    }
+
+    /// Returns the enabled [`BuiltinRoute`] addressed by a request to a
+    /// device with the given main route.
+    #[must_use]
+    pub fn resolve(
+        self,
+        main_route: &'static str,
+        route: &str,
+        rest_kind: RestKind,
+    ) -> Option<BuiltinRoute> {
+        if rest_kind != RestKind::Get {
+            return None;
+        }
+        self.routes()
+            .find(|builtin| builtin.path(main_route) == route)
+    }
+
+    const fn enable(mut self, route: BuiltinRoute) -> Self {
+        self.routes |= 1 << route as u8;
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::collections::Hashed;
+    use crate::device::{DeviceData, DeviceEnvironment, DeviceKind};
+    use crate::error::ErrorKind;
+    use crate::hazards::{Hazard, Hazards};
+    use crate::parameters::Parameters;
+    use crate::response::{HazardsResponse, HealthResponse, HealthStatus, ResponseKind};
+    use crate::route::{RestKind, Route, RouteConfigs, RouteConfigsBuilder};
+    use crate::serialize;
+
+    use super::{BuiltinRoute, HEALTH_ROUTE, INFO_ROUTE, Introspection};
+
+    fn device() -> DeviceData<4, 2, 8> {
+        let route_configs = RouteConfigsBuilder::<4, 2, 8>::new()
+            .route(
+                Route::put("/on")
+                    .with_hazards(Hazards::init(Hazard::FireHazard).insert(Hazard::SpendMoney))
+                    .with_parameters(Parameters::new()),
+            )
+            .unwrap()
+            .route(
+                Route::put("/off")
+                    .with_hazards(Hazards::init(Hazard::FireHazard))
+                    .with_parameters(Parameters::new()),
+            )
+            .unwrap()
+            .build();
+
+        DeviceData::new(
+            DeviceKind::Light,
+            DeviceEnvironment::Esp32,
+            "/light",
+            route_configs,
+        )
+    }
+
+    #[test]
+    fn test_register() {
+        let mut device = device();
+        Introspection::none()
+            .with_description()
+            .with_health()
+            .register(&mut device)
+            .unwrap();
+        assert_eq!(device.route_configs.len(), 4);
+
+        let description = device
+            .route_configs
+            .iter()
+            .find(|route_config| route_config.route() == "/light")
+            .unwrap();
+        assert_eq!(description.kind(), RestKind::Get);
+        assert_eq!(description.response_kind(), ResponseKind::Serial);
+
+        // Built-in routes cannot be registered twice, and the routes
+        // preceding the duplicate are not registered either.
+        assert_eq!(
+            Introspection::none()
+                .with_info()
+                .with_health()
+                .register(&mut device)
+                .unwrap_err()
+                .kind(),
+            ErrorKind::DuplicateRoute
+        );
+        assert_eq!(device.route_configs.len(), 4);
+
+        Introspection::none()
+            .with_info()
+            .register(&mut device)
+            .unwrap();
+        assert_eq!(
+            BuiltinRoute::Info
+                .route_config::<4, 2, Hashed>("/light")
+                .response_kind(),
+            ResponseKind::Info
+        );
+    }
+
+    #[test]
+    fn test_register_atomic() {
+        let register = |main_route, introspection: Introspection| {
+            let mut device = device();
+            device.main_route = main_route;
+            let error = introspection.register(&mut device).unwrap_err().kind();
+            assert_eq!(device.route_configs.len(), 2);
+            error
+        };
+
+        // A main route is checked as any other route.
+        assert_eq!(register("light", Introspection::all()), ErrorKind::Route);
+        // A main route can clash with another built-in route.
+        assert_eq!(
+            register(INFO_ROUTE, Introspection::all()),
+            ErrorKind::DuplicateRoute
+        );
+
+        // The capacity must be enough to contain all built-in routes.
+        let mut small = DeviceData::new(
+            DeviceKind::Light,
+            DeviceEnvironment::Esp32,
+            "/light",
+            device()
+                .route_configs
+                .iter()
+                .cloned()
+                .collect::<RouteConfigs<4, 2, 4>>(),
+        );
+        assert_eq!(
+            Introspection::all()
+                .register(&mut small)
+                .unwrap_err()
+                .kind(),
+            ErrorKind::Capacity
+        );
+        assert_eq!(small.route_configs.len(), 2);
+        Introspection::none()
+            .with_description()
+            .with_health()
+            .register(&mut small)
+            .unwrap();
+        assert_eq!(small.route_configs.len(), 4);
+    }
+
+    #[test]
+    fn test_resolve() {
+        let introspection = Introspection::none().with_description().with_health();
+
+        assert_eq!(
+            introspection.resolve("/light", "/light", RestKind::Get),
+            Some(BuiltinRoute::Description)
+        );
+        assert_eq!(
+            introspection.resolve("/light", HEALTH_ROUTE, RestKind::Get),
+            Some(BuiltinRoute::Health)
+        );
+        assert_eq!(
+            introspection.resolve("/light", HEALTH_ROUTE, RestKind::Put),
+            None
+        );
+        assert_eq!(
+            introspection.resolve("/light", INFO_ROUTE, RestKind::Get),
+            None
+        );
+        assert_eq!(Introspection::all().routes().count(), 4);
+    }
+
+    #[test]
+    fn test_builtin_responses() {
+        let device = device();
+
+        assert_eq!(
+            serialize(HazardsResponse::new(&device.route_configs)),
+            json!({
+                "Safety": ["FireHazard"],
+                "Financial": ["SpendMoney"],
+                "Privacy": []
+            })
+        );
+
+        assert_eq!(
+            serialize(HealthResponse::new(3600).with_status(HealthStatus::Degraded)),
+            json!({
+                "status": "Degraded",
+                "uptime": 3600
+            })
+        );
+    }
+}
@@ -43,6 +43,21 @@
 #![deny(missing_docs)]
 #![no_std]
 
+// Allows the macros to refer to this crate within its own tests.
+#[cfg(all(test, feature = "macros"))]
+extern crate self as tosca_stack;
+
+/// Access levels of route callers.
+pub mod access;
+/// Operating costs accounting.
+pub mod accounting;
+/// Audit log of route invocations.
+pub mod audit;
+/// Authentication of requests signed with a pre-shared key.
+#[cfg(feature = "auth")]
+pub mod auth;
+/// Localized descriptions.
+pub mod description;
 /// Description of a device with its routes information.
 pub mod device;
 /// Information about the economy device aspects.
@@ -51,14 +66,24 @@ pub mod economy;
 pub mod energy;
 /// Error handling.
 pub mod error;
+/// Events occurring on a device.
+pub mod events;
 /// Hazards descriptions and methods.
 pub mod hazards;
+/// Built-in introspection routes.
+pub mod introspection;
+/// Runtime energy measurements.
+pub mod metering;
 /// Route input parameters.
 pub mod parameters;
+/// Rate limits of route invocations.
+pub mod rate_limit;
 /// All supported responses returned by a device action.
 pub mod response;
 /// Definition of device routes.
 pub mod route;
+/// Semantic versions.
+pub mod version;
 
 // All fixed-capacity structures and collections.
 mod utils;
@@ -0,0 +1,395 @@
+use heapless::Deque;
+
+use serde::Serialize;
+
+use crate::energy::Energy;
+
+// Seconds in an hour, used to convert watt-seconds into watt-hours.
+const SECONDS_PER_HOUR: f64 = 3600.;
+
+/// Energy consumed within a time interval.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct EnergyBucket {
+    /// Interval start, expressed in seconds.
+    pub start: u64,
+    /// Interval duration, expressed in seconds.
+    pub duration: u32,
+    /// Energy consumed within the interval, expressed in watt-hours (Wh).
+    pub energy: f64,
+}
+
+impl EnergyBucket {
+    /// Returns the energy consumed within the interval, expressed in
+    /// kilowatt-hours (kWh).
+    #[must_use]
+    pub fn energy_kwh(&self) -> f64 {
+        self.energy / 1000.
+    }
+}
+
+/// An energy meter which accumulates the energy consumed by a device.
+///
+/// The consumed energy is accumulated into `B` time buckets of the same
+/// duration. When all buckets are filled, the oldest bucket is discarded to
+/// make room for a new one. The total consumed energy also includes the
+/// energy of discarded buckets.
+///
+/// Timestamps are expressed in seconds and their origin is chosen by the
+/// device, for example the device boot or the Unix epoch.
+#[derive(Debug, Clone, Serialize)]
+pub struct EnergyMeter<const B: usize> {
+    /// Last sampled power, expressed in watts (W).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    power: Option<f32>,
+    /// Total consumed energy, expressed in watt-hours (Wh).
+    #[serde(rename = "total energy")]
+    total_energy: f64,
+    /// Duration of each bucket, expressed in seconds.
+    #[serde(rename = "bucket duration")]
+    bucket_duration: u32,
+    /// Buckets of consumed energy, from the oldest to the newest.
+    buckets: Deque<EnergyBucket, B>,
+    // Timestamp of the last power sample.
+    #[serde(skip)]
+    last_sample: Option<u64>,
+}
+
+impl<const B: usize> EnergyMeter<B> {
+    /// Creates an [`EnergyMeter`] with buckets of the given duration,
+    /// expressed in seconds.
+    ///
+    /// A duration of zero seconds is replaced with one second.
+    #[must_use]
+    pub const fn new(bucket_duration: u32) -> Self {
+        Self {
+            power: None,
+            total_energy: 0.,
+            bucket_duration: if bucket_duration == 0 {
+                1
+            } else {
+                bucket_duration
+            },
+            buckets: Deque::new(),
+            last_sample: None,
+        }
+    }
+
+    /// Records a power sample, expressed in watts (W), taken at the given
+    /// timestamp.
+    ///
+    /// The energy consumed since the previous sample is computed with the
+    /// trapezoidal rule and distributed among the buckets covering that
+    /// interval. A sample older than the previous one only updates the
+    /// current power.
+    pub fn sample_power(&mut self, timestamp: u64, power: f32) {
+        if let (Some(last_power), Some(last_sample)) = (self.power, self.last_sample)
+            && timestamp > last_sample
+        {
+            let elapsed = u32::try_from(timestamp - last_sample).unwrap_or(u32::MAX);
+            let energy = f64::midpoint(f64::from(last_power), f64::from(power))
+                * f64::from(elapsed)
+                / SECONDS_PER_HOUR;
+            self.distribute(last_sample, timestamp, energy);
+        }
+
+        if self
+            .last_sample
+            .is_none_or(|last_sample| timestamp >= last_sample)
+        {
+            self.last_sample = Some(timestamp);
+        }
+        self.power = Some(power);
+    }
+
+    /// Adds an amount of energy, expressed in watt-hours (Wh), consumed at
+    /// the given timestamp.
+    ///
+    /// It is useful for devices equipped with a hardware energy counter.
+    /// Energy consumed before the oldest bucket is only added to the total
+    /// consumed energy.
+    pub fn add_energy(&mut self, timestamp: u64, energy: f64) {
+        self.total_energy += energy;
+        self.add_to_bucket(timestamp, energy);
+    }
+
+    /// Returns the last sampled power, expressed in watts (W).
+    #[must_use]
+    pub const fn power(&self) -> Option<f32> {
+        self.power
+    }
+
+    /// Returns the total consumed energy, expressed in watt-hours (Wh).
+    #[must_use]
+    pub const fn total_energy(&self) -> f64 {
+        self.total_energy
+    }
+
+    /// Returns the total consumed energy, expressed in kilowatt-hours (kWh).
+    #[must_use]
+    pub fn total_energy_kwh(&self) -> f64 {
+        self.total_energy / 1000.
+    }
+
+    /// Returns the duration of each bucket, expressed in seconds.
+    #[must_use]
+    pub const fn bucket_duration(&self) -> u32 {
+        self.bucket_duration
+    }
+
+    /// Returns an iterator over the [`EnergyBucket`]s, from the oldest to
+    /// the newest.
+    pub fn buckets(&self) -> impl Iterator<Item = &EnergyBucket> {
+        self.buckets.iter()
+    }
+
+    /// Returns the energy, expressed in watt-hours (Wh), consumed within
+    /// the buckets starting at or after the given timestamp.
+    #[must_use]
+    pub fn energy_since(&self, timestamp: u64) -> f64 {
+        self.buckets
+            .iter()
+            .filter(|bucket| bucket.start >= self.bucket_start(timestamp))
+            .map(|bucket| bucket.energy)
+            .sum()
+    }
+
+    /// Removes all measurements.
+    pub fn reset(&mut self) {
+        *self = Self::new(self.bucket_duration);
+    }
+
+    // Distributes the energy consumed within an interval among the buckets
+    // covering it, proportionally to their overlap with the interval.
+    fn distribute(&mut self, from: u64, to: u64, energy: f64) {
+        self.total_energy += energy;
+
+        let length = to - from;
+        let duration = u64::from(self.bucket_duration);
+        // Buckets which would be immediately discarded are skipped.
+        let oldest = self
+            .bucket_start(to)
+            .saturating_sub(duration * (B.saturating_sub(1) as u64));
+
+        let mut start = from.max(oldest);
+        while start < to {
+            let end = (self.bucket_start(start) + duration).min(to);
+            let overlap = u32::try_from(end - start).unwrap_or(u32::MAX);
+            self.add_to_bucket(
+                start,
+                energy * f64::from(overlap) / f64::from(u32::try_from(length).unwrap_or(u32::MAX)),
+            );
+            start = end;
+        }
+    }
+
+    fn add_to_bucket(&mut self, timestamp: u64, energy: f64) {
+        let start = self.bucket_start(timestamp);
+
+        if let Some(bucket) = self.buckets.iter_mut().find(|bucket| bucket.start == start) {
+            bucket.energy += energy;
+            return;
+        }
+
+        // Energy consumed before the oldest bucket of a full meter is only
+        // added to the total consumed energy.
+        if self.buckets.is_full()
+            && self
+                .buckets
+                .front()
+                .is_none_or(|bucket| start < bucket.start)
+        {
+            return;
+        }
+
+        if self.buckets.is_full() {
+            self.buckets.pop_front();
+        }
+
+        // Buckets are kept sorted by their start, so newer buckets are
+        // temporarily removed to place a bucket falling between them.
+        let mut newer = Deque::<EnergyBucket, B>::new();
+        while self
+            .buckets
+            .back()
+            .is_some_and(|bucket| bucket.start > start)
+        {
+            if let Some(bucket) = self.buckets.pop_back() {
+                let _ = newer.push_front(bucket);
+            }
+        }
+
+        let _ = self.buckets.push_back(EnergyBucket {
+            start,
+            duration: self.bucket_duration,
+            energy,
+        });
+
+        while let Some(bucket) = newer.pop_front() {
+            let _ = self.buckets.push_back(bucket);
+        }
+    }
+
+    fn bucket_start(&self, timestamp: u64) -> u64 {
+        timestamp - timestamp % u64::from(self.bucket_duration)
+    }
+}
+
+/// Energy information of a device together with its runtime measurements.
+///
+/// It is serialized as the [`Energy`] information with an additional
+/// `measurements` field, so a controller which does not know measurements
+/// can still read the [`Energy`] information.
+#[derive(Debug, Serialize)]
+pub struct EnergyReport<'a, const E: usize, const CF: usize, const B: usize> {
+    /// Energy information.
+    #[serde(flatten)]
+    pub energy: &'a Energy<E, CF>,
+    /// Runtime energy measurements.
+    pub measurements: &'a EnergyMeter<B>,
+}
+
+impl<'a, const E: usize, const CF: usize, const B: usize> EnergyReport<'a, E, CF, B> {
+    /// Creates an [`EnergyReport`].
+    #[must_use]
+    pub const fn new(energy: &'a Energy<E, CF>, measurements: &'a EnergyMeter<B>) -> Self {
+        Self {
+            energy,
+            measurements,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::energy::Energy;
+    use crate::serialize;
+
+    use super::{EnergyBucket, EnergyMeter, EnergyReport};
+
+    fn assert_energy(energy: f64, expected: f64) {
+        assert!(
+            (energy - expected).abs() < 1e-9,
+            "{energy} is not equal to {expected}"
+        );
+    }
+
+    #[test]
+    fn test_sample_power() {
+        let mut meter = EnergyMeter::<4>::new(3600);
+
+        meter.sample_power(0, 100.);
+        assert_eq!(meter.power(), Some(100.));
+        assert_eq!(meter.buckets().count(), 0);
+
+        // One hour and a half at 100 W, split between two buckets.
+        meter.sample_power(5400, 100.);
+        assert_energy(meter.total_energy(), 150.);
+        assert_energy(meter.total_energy_kwh(), 0.15);
+
+        {
+            let mut buckets = meter.buckets();
+            assert_eq!(
+                buckets.next(),
+                Some(&EnergyBucket {
+                    start: 0,
+                    duration: 3600,
+                    energy: 100.,
+                })
+            );
+            assert_eq!(
+                buckets.next(),
+                Some(&EnergyBucket {
+                    start: 3600,
+                    duration: 3600,
+                    energy: 50.,
+                })
+            );
+            assert_eq!(buckets.next(), None);
+        }
+
+        // Power grows linearly from 100 W to 300 W in half an hour.
+        meter.sample_power(7200, 300.);
+        assert_energy(meter.total_energy(), 250.);
+        assert_energy(meter.energy_since(3600), 150.);
+
+        // An older sample only updates the current power.
+        meter.sample_power(3600, 0.);
+        assert_eq!(meter.power(), Some(0.));
+        assert_energy(meter.total_energy(), 250.);
+    }
+
+    #[test]
+    fn test_discarded_buckets() {
+        let mut meter = EnergyMeter::<2>::new(60);
+
+        meter.add_energy(0, 1.);
+        meter.add_energy(60, 2.);
+        meter.add_energy(130, 4.);
+
+        assert_energy(meter.total_energy(), 7.);
+        assert_eq!(meter.buckets().map(|bucket| bucket.start).max(), Some(120));
+        assert_energy(meter.energy_since(0), 6.);
+
+        // Energy older than the oldest bucket is only added to the total.
+        meter.add_energy(0, 8.);
+        assert_energy(meter.total_energy(), 15.);
+        assert_energy(meter.energy_since(0), 6.);
+
+        meter.reset();
+        assert_energy(meter.total_energy(), 0.);
+        assert_eq!(meter.bucket_duration(), 60);
+    }
+
+    #[test]
+    fn test_gap_buckets() {
+        let mut meter = EnergyMeter::<4>::new(60);
+
+        meter.add_energy(0, 1.);
+        meter.add_energy(180, 2.);
+
+        // Energy falling between two buckets creates its own bucket.
+        meter.add_energy(70, 4.);
+        assert_energy(meter.total_energy(), 7.);
+        assert_energy(meter.energy_since(0), 7.);
+        assert!(meter.buckets().map(|bucket| bucket.start).eq([0, 60, 180]));
+
+        // In a full meter, the oldest bucket makes room for the new one.
+        meter.add_energy(240, 8.);
+        meter.add_energy(120, 16.);
+        assert_energy(meter.total_energy(), 31.);
+        assert_energy(meter.energy_since(0), 30.);
+        assert!(
+            meter
+                .buckets()
+                .map(|bucket| bucket.start)
+                .eq([60, 120, 180, 240])
+        );
+    }
+
+    #[test]
+    fn test_energy_report() {
+        let mut meter = EnergyMeter::<2>::new(60);
+        meter.add_energy(30, 2.5);
+        meter.sample_power(60, 10.);
+
+        assert_eq!(
+            serialize(EnergyReport::new(&Energy::<2, 2>::empty(), &meter)),
+            json!({
+                "measurements": {
+                    "power": 10.0,
+                    "total energy": 2.5,
+                    "bucket duration": 60,
+                    "buckets": [
+                        {
+                            "start": 0,
+                            "duration": 60,
+                            "energy": 2.5
+                        }
+                    ]
+                }
+            })
+        );
+    }
+}
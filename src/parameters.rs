@@ -1,9 +1,17 @@
-use serde::{Deserialize, Serialize};
+use core::str::FromStr;
 
-use crate::collections::{Map, SerialMap};
+use serde::ser::{SerializeMap, SerializeStructVariant};
+use serde::{Deserialize, Serialize, Serializer};
+
+use crate::collections::{Backing, Hashed, Map, SerialMap};
+use crate::description::Descriptions;
+use crate::error::{Error, ErrorKind, Result};
+
+#[cfg(feature = "macros")]
+pub use tosca_stack_macros::ToscaParameters;
 
 /// All supported kinds of route input parameters.
-#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
 pub enum ParameterKind {
     /// A [`bool`] value.
     Bool {
@@ -73,25 +81,262 @@ pub enum ParameterKind {
     },
 }
 
+impl ParameterKind {
+    /// Returns the default [`ParameterValue`] of a [`ParameterKind`].
+    #[must_use]
+    pub const fn default_value(&self) -> ParameterValue {
+        match *self {
+            Self::Bool { default } => ParameterValue::Bool(default),
+            Self::U8 { default } => ParameterValue::U8(default),
+            Self::U16 { default } => ParameterValue::U16(default),
+            Self::U32 { default } => ParameterValue::U32(default),
+            Self::U64 { default } | Self::RangeU64 { default, .. } => ParameterValue::U64(default),
+            Self::F32 { default } => ParameterValue::F32(default),
+            Self::F64 { default } | Self::RangeF64 { default, .. } => ParameterValue::F64(default),
+        }
+    }
+
+    /// Parses a textual input value according to the [`ParameterKind`].
+    ///
+    /// # Errors
+    ///
+    /// If the text does not represent a value of the [`ParameterKind`] type,
+    /// if a floating-point value is not finite, or if the value lies outside
+    /// of a range, an error is returned.
+    pub fn parse(&self, text: &str) -> Result<ParameterValue> {
+        let value = match *self {
+            Self::Bool { .. } => ParameterValue::Bool(parse_text(text)?),
+            Self::U8 { .. } => ParameterValue::U8(parse_text(text)?),
+            Self::U16 { .. } => ParameterValue::U16(parse_text(text)?),
+            Self::U32 { .. } => ParameterValue::U32(parse_text(text)?),
+            Self::U64 { .. } => ParameterValue::U64(parse_text(text)?),
+            Self::F32 { .. } => {
+                let value: f32 = parse_text(text)?;
+                if !value.is_finite() {
+                    return Err(not_finite());
+                }
+                ParameterValue::F32(value)
+            }
+            Self::F64 { .. } => ParameterValue::F64(parse_finite(text)?),
+            Self::RangeU64 { min, max, .. } => {
+                let value = parse_text(text)?;
+                if value < min || value > max {
+                    return Err(out_of_range());
+                }
+                ParameterValue::U64(value)
+            }
+            Self::RangeF64 { min, max, .. } => {
+                let value = parse_finite(text)?;
+                if !(min..=max).contains(&value) {
+                    return Err(out_of_range());
+                }
+                ParameterValue::F64(value)
+            }
+        };
+        Ok(value)
+    }
+}
+
+// A controller fills the missing bounds of a numeric parameter with the
+// whole type range and always serializes them, so the bounds are written
+// explicitly to produce the same output.
+impl Serialize for ParameterKind {
+    fn serialize<S: Serializer>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error> {
+        match *self {
+            Self::Bool { default } => {
+                let mut variant =
+                    serializer.serialize_struct_variant("ParameterKind", 0, "Bool", 1)?;
+                variant.serialize_field("default", &default)?;
+                variant.end()
+            }
+            Self::U8 { default } => serialize_integer(serializer, 1, "U8", default, u8::MAX),
+            Self::U16 { default } => serialize_integer(serializer, 2, "U16", default, u16::MAX),
+            Self::U32 { default } => serialize_integer(serializer, 3, "U32", default, u32::MAX),
+            Self::U64 { default } => serialize_integer(serializer, 4, "U64", default, u64::MAX),
+            Self::F32 { default } => serialize_float(serializer, 5, "F32", default),
+            Self::F64 { default } => serialize_float(serializer, 6, "F64", default),
+            Self::RangeU64 {
+                min,
+                max,
+                step,
+                default,
+            } => serialize_range(serializer, 7, "RangeU64", (min, max, step), default),
+            Self::RangeF64 {
+                min,
+                max,
+                step,
+                default,
+            } => serialize_range(serializer, 8, "RangeF64", (min, max, step), default),
+        }
+    }
+}
+
+fn serialize_integer<S: Serializer, T: Copy + Default + Serialize>(
+    serializer: S,
+    index: u32,
+    name: &'static str,
+    default: T,
+    max: T,
+) -> core::result::Result<S::Ok, S::Error> {
+    let mut variant = serializer.serialize_struct_variant("ParameterKind", index, name, 3)?;
+    variant.serialize_field("default", &default)?;
+    variant.serialize_field("min", &T::default())?;
+    variant.serialize_field("max", &max)?;
+    variant.end()
+}
+
+// The type range of a float parameter is omitted by a controller, while its
+// step is always serialized.
+fn serialize_float<S: Serializer, T: Copy + Default + Serialize>(
+    serializer: S,
+    index: u32,
+    name: &'static str,
+    default: T,
+) -> core::result::Result<S::Ok, S::Error> {
+    let mut variant = serializer.serialize_struct_variant("ParameterKind", index, name, 2)?;
+    variant.serialize_field("default", &default)?;
+    variant.serialize_field("step", &T::default())?;
+    variant.end()
+}
+
+fn serialize_range<S: Serializer, T: Copy + Serialize>(
+    serializer: S,
+    index: u32,
+    name: &'static str,
+    (min, max, step): (T, T, T),
+    default: T,
+) -> core::result::Result<S::Ok, S::Error> {
+    let mut variant = serializer.serialize_struct_variant("ParameterKind", index, name, 4)?;
+    variant.serialize_field("min", &min)?;
+    variant.serialize_field("max", &max)?;
+    variant.serialize_field("step", &step)?;
+    variant.serialize_field("default", &default)?;
+    variant.end()
+}
+
+fn parse_text<T: FromStr>(text: &str) -> Result<T> {
+    text.trim().parse().map_err(|_| {
+        Error::new(
+            ErrorKind::Parameter,
+            "Impossible to parse the input value of a route parameter.",
+        )
+    })
+}
+
+// Parses a floating-point value, rejecting `NaN` and infinities, which
+// would pass any range check.
+fn parse_finite(text: &str) -> Result<f64> {
+    let value: f64 = parse_text(text)?;
+    if !value.is_finite() {
+        return Err(not_finite());
+    }
+    Ok(value)
+}
+
+fn not_finite() -> Error {
+    Error::new(
+        ErrorKind::Parameter,
+        "The input value of a route parameter is not a finite number.",
+    )
+}
+
+fn out_of_range() -> Error {
+    Error::new(
+        ErrorKind::Parameter,
+        "The input value of a route parameter is out of range.",
+    )
+}
+
+/// A route input parameter value.
+///
+/// It is serialized as the bare value.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum ParameterValue {
+    /// A [`bool`] value.
+    Bool(bool),
+    /// An [`u8`] value.
+    U8(u8),
+    /// An [`u16`] value.
+    U16(u16),
+    /// An [`u32`] value.
+    U32(u32),
+    /// An [`u64`] value.
+    U64(u64),
+    /// A [`f32`] value.
+    F32(f32),
+    /// A [`f64`] value.
+    F64(f64),
+}
+
+macro_rules! from_parameter_value {
+    ($ty:ty, $variant:ident) => {
+        impl TryFrom<ParameterValue> for $ty {
+            type Error = Error;
+
+            fn try_from(value: ParameterValue) -> Result<Self> {
+                match value {
+                    ParameterValue::$variant(value) => Ok(value),
+                    _ => Err(Error::new(
+                        ErrorKind::Parameter,
+                        concat!(
+                            "The route parameter value is not a `",
+                            stringify!($ty),
+                            "` value."
+                        ),
+                    )),
+                }
+            }
+        }
+    };
+}
+
+from_parameter_value!(bool, Bool);
+from_parameter_value!(u8, U8);
+from_parameter_value!(u16, U16);
+from_parameter_value!(u32, U32);
+from_parameter_value!(u64, U64);
+from_parameter_value!(f32, F32);
+from_parameter_value!(f64, F64);
+
 /// A map of serializable [`Parameters`] data.
-pub type ParametersData<const N: usize> = SerialMap<&'static str, ParameterKind, N>;
+pub type ParametersData<const N: usize, B = Hashed> = SerialMap<&'static str, ParameterKind, N, B>;
+
+/// A map of serializable localized descriptions of [`Parameters`].
+pub type ParametersDescriptions<const N: usize, B = Hashed> =
+    SerialMap<&'static str, Descriptions, N, B>;
+
+// A parameter kind stored together with its localized descriptions.
+#[derive(Debug, Clone, Copy)]
+struct Parameter {
+    kind: ParameterKind,
+    descriptions: Descriptions,
+}
 
 /// Route input parameters.
+///
+/// **For alignment reasons, with the default [`Hashed`] backing it accepts
+/// only a power of two as number of elements.** With a
+/// [`Linear`](crate::collections::Linear) backing, it accepts any number of
+/// elements, zero included.
 #[derive(Debug, Clone)]
-pub struct Parameters<const N: usize>(Map<&'static str, ParameterKind, N>);
+pub struct Parameters<const N: usize, B: Backing = Hashed> {
+    parameters: Map<&'static str, Parameter, N, B>,
+}
 
-impl<const N: usize> Default for Parameters<N> {
+impl<const N: usize, B: Backing> Default for Parameters<N, B> {
     fn default() -> Self {
         Self::new()
     }
 }
 
-impl<const N: usize> Parameters<N> {
+impl<const N: usize, B: Backing> Parameters<N, B> {
     /// Creates a [`Parameters`].
     #[must_use]
-    #[inline]
-    pub fn new() -> Self {
-        Self(Map::new())
+    pub const fn new() -> Self {
+        Self {
+            parameters: Map::new(),
+        }
     }
 
     /// Adds a [`bool`] parameter.
@@ -197,29 +442,395 @@ impl<const N: usize> Parameters<N> {
         )
     }
 
+    /// Sets the localized descriptions of a parameter.
+    ///
+    /// Each entry associates a language tag with a description text.
+    /// Descriptions of a parameter which has not been added yet are
+    /// discarded.
+    #[must_use]
+    #[inline]
+    pub fn localized_descriptions(
+        mut self,
+        name: &'static str,
+        descriptions: &'static [(&'static str, &'static str)],
+    ) -> Self {
+        if let Some(parameter) = self.parameters.get_mut(&name) {
+            parameter.descriptions = Descriptions::new(descriptions);
+        }
+        self
+    }
+
+    /// Returns the localized [`Descriptions`] of a parameter.
+    #[must_use]
+    pub fn descriptions(&self, name: &str) -> Option<&Descriptions> {
+        self.parameter(name)
+            .map(|parameter| &parameter.descriptions)
+            .filter(|descriptions| !descriptions.is_empty())
+    }
+
     /// Serializes [`Parameters`] data.
     ///
     /// It consumes the data.
     #[must_use]
     #[inline]
-    pub fn serialize_data(self) -> ParametersData<N> {
+    pub fn serialize_data(self) -> ParametersData<N, B> {
         let mut data = ParametersData::new();
-        for (key, value) in &self.0 {
-            data.add(key, *value);
+        for (key, parameter) in &self.parameters {
+            data.add(key, parameter.kind);
         }
         data
     }
 
-    fn create_parameter(self, name: &'static str, parameter_kind: ParameterKind) -> Self {
-        Self(self.0.insert(name, parameter_kind))
+    /// Serializes the localized descriptions of [`Parameters`].
+    #[must_use]
+    #[inline]
+    pub fn serialize_descriptions(&self) -> ParametersDescriptions<N, B> {
+        let mut data = ParametersDescriptions::new();
+        for (key, descriptions) in self.described() {
+            data.add(key, *descriptions);
+        }
+        data
+    }
+
+    /// Returns the [`ParameterKind`] associated with a parameter name.
+    #[must_use]
+    pub fn kind(&self, name: &str) -> Option<&ParameterKind> {
+        self.parameter(name).map(|parameter| &parameter.kind)
+    }
+
+    /// Returns the default [`ParameterValue`] of a parameter.
+    ///
+    /// # Errors
+    ///
+    /// If the parameter does not exist, an error is returned.
+    pub fn default_value(&self, name: &str) -> Result<ParameterValue> {
+        self.existing_kind(name).map(ParameterKind::default_value)
+    }
+
+    /// Parses the textual input value of a parameter.
+    ///
+    /// # Errors
+    ///
+    /// If the parameter does not exist or its value is not valid,
+    /// an error is returned.
+    pub fn parse(&self, name: &str, text: &str) -> Result<ParameterValue> {
+        self.existing_kind(name)?.parse(text)
+    }
+
+    fn existing_kind(&self, name: &str) -> Result<&ParameterKind> {
+        self.kind(name)
+            .ok_or_else(|| Error::new(ErrorKind::Parameter, "The route parameter does not exist."))
+    }
+
+    fn parameter(&self, name: &str) -> Option<&Parameter> {
+        self.parameters
+            .iter()
+            .find_map(|(key, parameter)| (*key == name).then_some(parameter))
     }
+
+    // Parameters with localized descriptions.
+    fn described(&self) -> impl Iterator<Item = (&'static str, &Descriptions)> {
+        self.parameters
+            .iter()
+            .filter(|(_, parameter)| !parameter.descriptions.is_empty())
+            .map(|(key, parameter)| (*key, &parameter.descriptions))
+    }
+
+    // A redefined parameter keeps its localized descriptions.
+    fn create_parameter(mut self, name: &'static str, parameter_kind: ParameterKind) -> Self {
+        match self.parameters.get_mut(&name) {
+            Some(parameter) => parameter.kind = parameter_kind,
+            None => self.parameters.add(
+                name,
+                Parameter {
+                    kind: parameter_kind,
+                    descriptions: Descriptions::empty(),
+                },
+            ),
+        }
+        self
+    }
+}
+
+/// A route input parameter which can be built in a `const` context.
+///
+/// A static slice of [`ParameterDescriptor`]s describes the input parameters
+/// of a [`RouteDescriptor`](crate::route::RouteDescriptor), so that it can be
+/// stored in read-only memory. Parameter names must be unique within a slice.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ParameterDescriptor {
+    name: &'static str,
+    kind: ParameterKind,
+    descriptions: Descriptions,
+}
+
+impl ParameterDescriptor {
+    /// Creates a [`bool`] parameter.
+    #[must_use]
+    pub const fn bool(name: &'static str, default: bool) -> Self {
+        Self::new(name, ParameterKind::Bool { default })
+    }
+
+    /// Creates an [`u8`] parameter.
+    #[must_use]
+    pub const fn u8(name: &'static str, default: u8) -> Self {
+        Self::new(name, ParameterKind::U8 { default })
+    }
+
+    /// Creates an [`u16`] parameter.
+    #[must_use]
+    pub const fn u16(name: &'static str, default: u16) -> Self {
+        Self::new(name, ParameterKind::U16 { default })
+    }
+
+    /// Creates an [`u32`] parameter.
+    #[must_use]
+    pub const fn u32(name: &'static str, default: u32) -> Self {
+        Self::new(name, ParameterKind::U32 { default })
+    }
+
+    /// Creates an [`u64`] parameter.
+    #[must_use]
+    pub const fn u64(name: &'static str, default: u64) -> Self {
+        Self::new(name, ParameterKind::U64 { default })
+    }
+
+    /// Creates a [`f32`] parameter.
+    #[must_use]
+    pub const fn f32(name: &'static str, default: f32) -> Self {
+        Self::new(name, ParameterKind::F32 { default })
+    }
+
+    /// Creates a [`f64`] parameter.
+    #[must_use]
+    pub const fn f64(name: &'static str, default: f64) -> Self {
+        Self::new(name, ParameterKind::F64 { default })
+    }
+
+    /// Creates an [`u64`] range without a default value.
+    #[must_use]
+    pub const fn rangeu64(name: &'static str, range: (u64, u64, u64)) -> Self {
+        Self::rangeu64_with_default(name, range, 0)
+    }
+
+    /// Creates an [`u64`] range with a default value.
+    #[must_use]
+    pub const fn rangeu64_with_default(
+        name: &'static str,
+        range: (u64, u64, u64),
+        default: u64,
+    ) -> Self {
+        Self::new(
+            name,
+            ParameterKind::RangeU64 {
+                min: range.0,
+                max: range.1,
+                step: range.2,
+                default,
+            },
+        )
+    }
+
+    /// Creates a [`f64`] range without a default value.
+    #[must_use]
+    pub const fn rangef64(name: &'static str, range: (f64, f64, f64)) -> Self {
+        Self::rangef64_with_default(name, range, 0.0)
+    }
+
+    /// Creates a [`f64`] range with a default value.
+    #[must_use]
+    pub const fn rangef64_with_default(
+        name: &'static str,
+        range: (f64, f64, f64),
+        default: f64,
+    ) -> Self {
+        Self::new(
+            name,
+            ParameterKind::RangeF64 {
+                min: range.0,
+                max: range.1,
+                step: range.2,
+                default,
+            },
+        )
+    }
+
+    /// Sets the localized descriptions of the parameter.
+    ///
+    /// Each entry associates a language tag with a description text.
+    #[must_use]
+    pub const fn localized_descriptions(
+        mut self,
+        descriptions: &'static [(&'static str, &'static str)],
+    ) -> Self {
+        self.descriptions = Descriptions::new(descriptions);
+        self
+    }
+
+    /// Returns the parameter name.
+    #[must_use]
+    pub const fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Returns the [`ParameterKind`].
+    #[must_use]
+    pub const fn kind(&self) -> &ParameterKind {
+        &self.kind
+    }
+
+    /// Returns the localized [`Descriptions`].
+    #[must_use]
+    pub const fn descriptions(&self) -> &Descriptions {
+        &self.descriptions
+    }
+
+    const fn new(name: &'static str, kind: ParameterKind) -> Self {
+        Self {
+            name,
+            kind,
+            descriptions: Descriptions::empty(),
+        }
+    }
+}
+
+// Serializes a slice of `ParameterDescriptor`s as `ParametersData`.
+pub(crate) struct ParametersTable(pub(crate) &'static [ParameterDescriptor]);
+
+impl Serialize for ParametersTable {
+    fn serialize<S: Serializer>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.0.len()))?;
+        for parameter in self.0 {
+            map.serialize_entry(parameter.name, &parameter.kind)?;
+        }
+        map.end()
+    }
+}
+
+// Serializes the localized descriptions of a slice of `ParameterDescriptor`s
+// as `ParametersDescriptions`.
+pub(crate) struct ParametersTableDescriptions(pub(crate) &'static [ParameterDescriptor]);
+
+impl ParametersTableDescriptions {
+    pub(crate) fn is_empty(&self) -> bool {
+        self.described().next().is_none()
+    }
+
+    fn described(&self) -> impl Iterator<Item = &ParameterDescriptor> {
+        self.0
+            .iter()
+            .filter(|parameter| !parameter.descriptions.is_empty())
+    }
+}
+
+impl Serialize for ParametersTableDescriptions {
+    fn serialize<S: Serializer>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.described().count()))?;
+        for parameter in self.described() {
+            map.serialize_entry(parameter.name, &parameter.descriptions)?;
+        }
+        map.end()
+    }
+}
+
+// Serializes `Parameters` as `ParametersData` without building a new map.
+pub(crate) struct ParametersMap<'a, const N: usize, B: Backing>(pub(crate) &'a Parameters<N, B>);
+
+impl<const N: usize, B: Backing> ParametersMap<'_, N, B> {
+    pub(crate) fn is_empty(&self) -> bool {
+        self.0.parameters.is_empty()
+    }
+}
+
+impl<const N: usize, B: Backing> Serialize for ParametersMap<'_, N, B> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.0.parameters.len()))?;
+        for (name, parameter) in &self.0.parameters {
+            map.serialize_entry(name, &parameter.kind)?;
+        }
+        map.end()
+    }
+}
+
+// Serializes the localized descriptions of `Parameters` as
+// `ParametersDescriptions` without building a new map.
+pub(crate) struct ParametersMapDescriptions<'a, const N: usize, B: Backing>(
+    pub(crate) &'a Parameters<N, B>,
+);
+
+impl<const N: usize, B: Backing> ParametersMapDescriptions<'_, N, B> {
+    pub(crate) fn is_empty(&self) -> bool {
+        self.0.described().next().is_none()
+    }
+}
+
+impl<const N: usize, B: Backing> Serialize for ParametersMapDescriptions<'_, N, B> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(self.0.described().count()))?;
+        for (name, descriptions) in self.0.described() {
+            map.serialize_entry(name, descriptions)?;
+        }
+        map.end()
+    }
+}
+
+/// Route input parameters described by a structure.
+///
+/// The structure fields define the [`Parameters`] schema of a route, while
+/// its values are built from the input parameters received by a device.
+///
+/// This trait is usually implemented through the `ToscaParameters` derive
+/// macro, which computes the `N` capacity from the number of fields.
+pub trait ToscaParameters<const N: usize>: Sized {
+    /// Returns the [`Parameters`] schema.
+    fn parameters() -> Parameters<N>;
+
+    /// Builds the structure from a sequence of `(name, value)` textual pairs.
+    ///
+    /// Missing parameters assume their default values.
+    ///
+    /// # Errors
+    ///
+    /// If a parameter does not exist or its value is not valid,
+    /// an error is returned.
+    fn parse<'a>(values: impl IntoIterator<Item = (&'a str, &'a str)>) -> Result<Self>;
 }
 
 #[cfg(test)]
 mod tests {
+    use crate::description::Descriptions;
+    use crate::error::ErrorKind;
     use crate::serialize;
 
-    use super::{ParameterKind, Parameters, SerialMap};
+    use super::{ParameterKind, ParameterValue, Parameters, SerialMap};
+
+    #[test]
+    fn test_parameter_descriptions() {
+        let parameters = Parameters::<2>::new()
+            .u8("mode", 0)
+            .localized_descriptions("mode", &[("en", "Mode")])
+            // Descriptions of a missing parameter are discarded.
+            .localized_descriptions("missing", &[("en", "Missing")])
+            // A redefined parameter keeps its descriptions.
+            .u8("mode", 1);
+
+        assert_eq!(
+            parameters.descriptions("mode").unwrap().get("en"),
+            Some("Mode")
+        );
+        assert!(parameters.descriptions("missing").is_none());
+        assert_eq!(
+            parameters.default_value("mode").unwrap(),
+            ParameterValue::U8(1)
+        );
+        assert_eq!(
+            serialize(parameters.serialize_descriptions()),
+            serialize(
+                SerialMap::<&'static str, _, 2>::new()
+                    .insert("mode", Descriptions::new(&[("en", "Mode")]))
+            )
+        );
+    }
 
     #[test]
     fn test_parameters() {
@@ -269,4 +880,101 @@ mod tests {
             serialize(parameters_data),
         );
     }
+
+    #[test]
+    fn test_parse_parameters() {
+        let parameters = Parameters::<4>::new()
+            .bool("bool", true)
+            .u8("u8", 0)
+            .rangeu64_with_default("rangeu64", (0, 20, 1), 5);
+
+        assert_eq!(
+            parameters.parse("bool", "false").unwrap(),
+            ParameterValue::Bool(false)
+        );
+        assert_eq!(
+            parameters.parse("u8", "42").unwrap(),
+            ParameterValue::U8(42)
+        );
+        assert_eq!(
+            parameters.parse("rangeu64", "20").unwrap(),
+            ParameterValue::U64(20)
+        );
+        assert_eq!(
+            parameters.default_value("rangeu64").unwrap(),
+            ParameterValue::U64(5)
+        );
+
+        // Out of range, wrong type and missing parameters.
+        assert!(parameters.parse("rangeu64", "21").is_err());
+        assert!(parameters.parse("u8", "256").is_err());
+        assert!(parameters.parse("missing", "0").is_err());
+    }
+
+    #[test]
+    fn test_parse_non_finite() {
+        let parameters = Parameters::<4>::new()
+            .f32("f32", 0.)
+            .f64("f64", 0.)
+            .rangef64("rangef64", (-10., 40., 0.5));
+
+        for name in ["f32", "f64", "rangef64"] {
+            for text in ["NaN", "nan", "inf", "-inf", "infinity"] {
+                assert_eq!(
+                    parameters.parse(name, text).unwrap_err().kind(),
+                    ErrorKind::Parameter
+                );
+            }
+        }
+        assert_eq!(
+            parameters.parse("rangef64", "40").unwrap(),
+            ParameterValue::F64(40.)
+        );
+    }
+
+    #[cfg(feature = "macros")]
+    #[test]
+    fn test_derive_parameters() {
+        use super::ToscaParameters;
+
+        #[derive(Debug, PartialEq, ToscaParameters)]
+        struct Light {
+            on: bool,
+            #[tosca(default = 3)]
+            mode: u8,
+            #[tosca(rename = "level", range(0, 20, 0.1), default = 5)]
+            brightness: f64,
+        }
+
+        let parameters_data = SerialMap::<&'static str, ParameterKind, 4>::new()
+            .insert("on", ParameterKind::Bool { default: false })
+            .insert("mode", ParameterKind::U8 { default: 3 })
+            .insert(
+                "level",
+                ParameterKind::RangeF64 {
+                    min: 0.,
+                    max: 20.,
+                    step: 0.1,
+                    default: 5.,
+                },
+            );
+
+        assert_eq!(
+            serialize(Light::parameters().serialize_data()),
+            serialize(parameters_data),
+        );
+
+        assert_eq!(
+            Light::parse([("on", "true"), ("level", "7.5")]).unwrap(),
+            Light {
+                on: true,
+                mode: 3,
+                brightness: 7.5,
+            }
+        );
+
+        assert!(Light::parse([("level", "21")]).is_err());
+        assert!(Light::parse([("level", "NaN")]).is_err());
+        assert!(Light::parse([("unknown", "1")]).is_err());
+    }
 }
@@ -0,0 +1,392 @@
+use serde::Serialize;
+
+use crate::collections::LinearMap;
+use crate::error::{Error, ErrorKind};
+use crate::response::ErrorResponse;
+use crate::route::{RestKind, Route};
+
+/// Rate limit of a route.
+///
+/// A route can be invoked at most `calls` times within a window, and two
+/// consecutive invocations must be separated by a minimum interval.
+/// Durations are expressed in milliseconds.
+///
+/// It protects the hardware from repeated invocations, for example the
+/// toggling of a relay or a heater. A route is only limited when it declares
+/// a rate limit through [`Route::with_rate_limit`], whatever its hazards.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+pub struct RateLimit {
+    /// Maximum number of invocations within a window.
+    calls: u16,
+    /// Window duration, expressed in milliseconds.
+    window: u32,
+    /// Minimum interval between two invocations, expressed in milliseconds.
+    #[serde(rename = "minimum interval")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    min_interval: Option<u32>,
+}
+
+impl RateLimit {
+    /// Creates a [`RateLimit`] which allows at most `calls` invocations
+    /// within a window, expressed in milliseconds.
+    ///
+    /// A number of calls or a window of zero is replaced with one.
+    #[must_use]
+    pub const fn new(calls: u16, window: u32) -> Self {
+        Self {
+            calls: if calls == 0 { 1 } else { calls },
+            window: if window == 0 { 1 } else { window },
+            min_interval: None,
+        }
+    }
+
+    /// Creates a [`RateLimit`] which only requires a minimum interval,
+    /// expressed in milliseconds, between two invocations.
+    #[must_use]
+    pub const fn cooldown(min_interval: u32) -> Self {
+        Self::new(1, min_interval).with_min_interval(min_interval)
+    }
+
+    /// Sets the minimum interval between two invocations, expressed in
+    /// milliseconds.
+    #[must_use]
+    pub const fn with_min_interval(mut self, min_interval: u32) -> Self {
+        self.min_interval = Some(min_interval);
+        self
+    }
+
+    /// Returns the maximum number of invocations within a window.
+    #[must_use]
+    pub const fn calls(&self) -> u16 {
+        self.calls
+    }
+
+    /// Returns the window duration, expressed in milliseconds.
+    #[must_use]
+    pub const fn window(&self) -> u32 {
+        self.window
+    }
+
+    /// Returns the minimum interval between two invocations, expressed in
+    /// milliseconds.
+    #[must_use]
+    pub const fn min_interval(&self) -> Option<u32> {
+        self.min_interval
+    }
+
+    // Tokens of a full bucket. An invocation consumes `window` tokens, while
+    // `calls` tokens are added every millisecond.
+    fn capacity(self) -> u64 {
+        u64::from(self.calls) * u64::from(self.window)
+    }
+}
+
+/// A rejected route invocation, because of a [`RateLimit`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Throttled {
+    /// Time to wait before invoking the route again, expressed in
+    /// milliseconds.
+    pub retry_after: u64,
+}
+
+impl Throttled {
+    /// Returns the time to wait before invoking the route again, rounded up
+    /// to seconds.
+    #[must_use]
+    pub fn retry_after_seconds(&self) -> u32 {
+        u32::try_from(self.retry_after.div_ceil(1000)).unwrap_or(u32::MAX)
+    }
+}
+
+impl<const N: usize> From<Throttled> for ErrorResponse<N> {
+    fn from(throttled: Throttled) -> Self {
+        Self::from_error(&Error::new(
+            ErrorKind::RateLimit,
+            "The route has been invoked too many times.",
+        ))
+        .retry_after(throttled.retry_after_seconds())
+    }
+}
+
+// Token bucket of a route.
+#[derive(Debug, Clone, Copy)]
+struct Bucket {
+    // Available tokens.
+    tokens: u64,
+    // Time of the last tokens update.
+    updated: u64,
+    // Time of the last invocation.
+    last_call: Option<u64>,
+}
+
+impl Bucket {
+    fn new(rate_limit: RateLimit, now: u64) -> Self {
+        Self {
+            tokens: rate_limit.capacity(),
+            updated: now,
+            last_call: None,
+        }
+    }
+
+    fn refill(&mut self, rate_limit: RateLimit, now: u64) {
+        // A clock which goes backwards does not add tokens.
+        let elapsed = now.saturating_sub(self.updated);
+        self.tokens = self
+            .tokens
+            .saturating_add(elapsed.saturating_mul(u64::from(rate_limit.calls)))
+            .min(rate_limit.capacity());
+        self.updated = self.updated.max(now);
+    }
+
+    // Time to wait before the next invocation, expressed in milliseconds.
+    fn wait(&self, rate_limit: RateLimit, now: u64) -> u64 {
+        let window = u64::from(rate_limit.window);
+        let tokens = if self.tokens >= window {
+            0
+        } else {
+            (window - self.tokens).div_ceil(u64::from(rate_limit.calls))
+        };
+        let interval =
+            self.last_call
+                .zip(rate_limit.min_interval)
+                .map_or(0, |(last_call, min_interval)| {
+                    u64::from(min_interval).saturating_sub(now.saturating_sub(last_call))
+                });
+        tokens.max(interval)
+    }
+
+    // An idle bucket is equivalent to a new one.
+    fn is_idle(&self, rate_limit: RateLimit, now: u64) -> bool {
+        self.tokens == rate_limit.capacity() && self.wait(rate_limit, now) == 0
+    }
+}
+
+/// A fixed-memory tracker of route invocations, which enforces
+/// [`RateLimit`]s through token buckets.
+///
+/// It tracks at most `N` routes. Time is supplied by the caller through a
+/// monotonic clock, expressed in milliseconds, for example the time elapsed
+/// since the device boot.
+///
+/// When all buckets are in use, the bucket of an idle route is reused. If
+/// there are no idle routes, the invocation of an untracked route is
+/// rejected for a whole window.
+#[derive(Debug, Clone)]
+pub struct RateLimiter<const N: usize> {
+    buckets: LinearMap<(&'static str, RestKind), (RateLimit, Bucket), N>,
+}
+
+impl<const N: usize> Default for RateLimiter<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> RateLimiter<N> {
+    /// Creates a [`RateLimiter`].
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            buckets: LinearMap::new(),
+        }
+    }
+
+    /// Records the invocation of a route with a [`RateLimit`] at the given
+    /// time, expressed in milliseconds.
+    ///
+    /// # Errors
+    ///
+    /// If the invocation exceeds the [`RateLimit`], it is not recorded and
+    /// the time to wait before invoking the route again is returned.
+    pub fn check(
+        &mut self,
+        route: &'static str,
+        rest_kind: RestKind,
+        rate_limit: RateLimit,
+        now: u64,
+    ) -> Result<(), Throttled> {
+        let key = (route, rest_kind);
+        if !self.buckets.contains_key(&key) {
+            self.track(key, rate_limit, now)?;
+        }
+
+        let Some((limit, bucket)) = self.buckets.get_mut(&key) else {
+            return Err(Throttled {
+                retry_after: u64::from(rate_limit.window),
+            });
+        };
+        // A changed rate limit restarts the bucket.
+        if *limit != rate_limit {
+            *limit = rate_limit;
+            *bucket = Bucket::new(rate_limit, now);
+        }
+
+        bucket.refill(rate_limit, now);
+        let retry_after = bucket.wait(rate_limit, now);
+        if retry_after > 0 {
+            return Err(Throttled { retry_after });
+        }
+
+        bucket.tokens -= u64::from(rate_limit.window);
+        bucket.last_call = Some(now);
+        Ok(())
+    }
+
+    /// Records the invocation of a [`Route`] at the given time, expressed in
+    /// milliseconds.
+    ///
+    /// A route without a [`RateLimit`] is never rejected.
+    ///
+    /// # Errors
+    ///
+    /// If the invocation exceeds the route [`RateLimit`], it is not recorded
+    /// and the time to wait before invoking the route again is returned.
+    pub fn check_route<const H: usize, const P: usize>(
+        &mut self,
+        route: &Route<H, P>,
+        now: u64,
+    ) -> Result<(), Throttled> {
+        match route.rate_limit() {
+            Some(rate_limit) => self.check(route.route(), route.kind(), rate_limit, now),
+            None => Ok(()),
+        }
+    }
+
+    /// Returns the number of tracked routes.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.buckets.len()
+    }
+
+    /// Checks whether there are no tracked routes.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.buckets.is_empty()
+    }
+
+    /// Removes all tracked routes.
+    pub fn clear(&mut self) {
+        self.buckets.clear();
+    }
+
+    fn track(
+        &mut self,
+        key: (&'static str, RestKind),
+        rate_limit: RateLimit,
+        now: u64,
+    ) -> Result<(), Throttled> {
+        if self.buckets.len() == N {
+            self.buckets.retain(|_, (limit, bucket)| {
+                bucket.refill(*limit, now);
+                !bucket.is_idle(*limit, now)
+            });
+        }
+        self.buckets
+            .try_add(key, (rate_limit, Bucket::new(rate_limit, now)))
+            .map_err(|_| Throttled {
+                retry_after: u64::from(rate_limit.window),
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::error::ErrorKind;
+    use crate::hazards::{Hazard, Hazards};
+    use crate::response::{ErrorKind as ResponseErrorKind, ErrorResponse};
+    use crate::route::{RestKind, Route};
+    use crate::serialize;
+
+    use super::{RateLimit, RateLimiter, Throttled};
+
+    #[test]
+    fn test_rate_limit() {
+        let mut limiter = RateLimiter::<2>::new();
+        let rate_limit = RateLimit::new(2, 1000);
+
+        assert!(
+            limiter
+                .check("/relay", RestKind::Put, rate_limit, 0)
+                .is_ok()
+        );
+        assert!(
+            limiter
+                .check("/relay", RestKind::Put, rate_limit, 100)
+                .is_ok()
+        );
+        assert_eq!(
+            limiter.check("/relay", RestKind::Put, rate_limit, 200),
+            Err(Throttled { retry_after: 300 })
+        );
+
+        // Half a window refills a token.
+        assert!(
+            limiter
+                .check("/relay", RestKind::Put, rate_limit, 500)
+                .is_ok()
+        );
+        // Routes are tracked separately.
+        assert!(
+            limiter
+                .check("/relay", RestKind::Get, rate_limit, 500)
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_cooldown() {
+        let mut limiter = RateLimiter::<1>::new();
+        let route = Route::put("/heater").with_rate_limit(RateLimit::cooldown(5000));
+
+        assert!(limiter.check_route(&route, 1000).is_ok());
+        let throttled = limiter.check_route(&route, 3000).unwrap_err();
+        assert_eq!(throttled.retry_after, 3000);
+
+        let response = ErrorResponse::<64>::from(throttled);
+        assert_eq!(response.error, ResponseErrorKind::InvalidData);
+        assert_eq!(response.code, Some(ErrorKind::RateLimit.code()));
+        assert_eq!(response.retry_after, Some(3));
+
+        // A route without a rate limit is never rejected, even when it has
+        // safety hazards.
+        assert!(limiter.check_route(&Route::get("/state"), 3000).is_ok());
+        let heater = Route::put("/heater").with_hazards(Hazards::<2>::init(Hazard::FireHazard));
+        assert_eq!(heater.rate_limit(), None);
+        assert!(limiter.check_route(&heater, 3000).is_ok());
+        assert!(limiter.check_route(&heater, 3000).is_ok());
+
+        // A minimum interval limits bursts.
+        let burst = RateLimit::new(10, 60_000).with_min_interval(1000);
+        let mut limiter = RateLimiter::<1>::new();
+        assert!(limiter.check("/relay", RestKind::Put, burst, 0).is_ok());
+        assert!(limiter.check("/relay", RestKind::Put, burst, 400).is_err());
+        assert!(limiter.check("/relay", RestKind::Put, burst, 1000).is_ok());
+
+        assert_eq!(
+            serialize(burst),
+            json!({
+                "calls": 10,
+                "window": 60_000,
+                "minimum interval": 1000
+            })
+        );
+    }
+
+    #[test]
+    fn test_full_rate_limiter() {
+        let mut limiter = RateLimiter::<1>::new();
+        let rate_limit = RateLimit::new(1, 1000);
+
+        assert!(limiter.check("/a", RestKind::Put, rate_limit, 0).is_ok());
+        // The only bucket is in use.
+        assert_eq!(
+            limiter.check("/b", RestKind::Put, rate_limit, 500),
+            Err(Throttled { retry_after: 1000 })
+        );
+        // The bucket is idle, so it is reused.
+        assert!(limiter.check("/b", RestKind::Put, rate_limit, 1000).is_ok());
+        assert_eq!(limiter.len(), 1);
+    }
+}
@@ -1,6 +1,12 @@
-use serde::{Deserialize, Serialize};
+use serde::ser::{SerializeMap, SerializeSeq};
+use serde::{Deserialize, Serialize, Serializer};
 
+use crate::collections::{Backing, Hashed};
 use crate::device::DeviceInfo;
+use crate::error::{Error, ErrorKind as StackErrorKind};
+use crate::hazards::{Category, Hazard};
+use crate::metering::EnergyReport;
+use crate::route::RouteConfigs;
 use crate::string::String;
 
 pub use tosca::response::{ErrorKind, OkResponse, ResponseKind, SerialResponse};
@@ -22,10 +28,152 @@ impl<const C: usize, const R: usize, const E: usize, const CF: usize> InfoRespon
     }
 }
 
+/// Energy response.
+///
+/// This response provides the energy information of a device together with
+/// its runtime measurements. It is serialized as an [`InfoResponse`]
+/// containing only energy information, so a controller which does not know
+/// measurements can still read it.
+#[derive(Debug, Serialize)]
+pub struct EnergyResponse<'a, const E: usize, const CF: usize, const B: usize> {
+    energy: EnergyReport<'a, E, CF, B>,
+}
+
+impl<'a, const E: usize, const CF: usize, const B: usize> EnergyResponse<'a, E, CF, B> {
+    /// Creates an [`EnergyResponse`].
+    #[must_use]
+    pub const fn new(energy: EnergyReport<'a, E, CF, B>) -> Self {
+        Self { energy }
+    }
+}
+
+/// Health status of a device.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum HealthStatus {
+    /// The device works correctly.
+    Ok,
+    /// Some device operations are not available.
+    Degraded,
+    /// The device cannot perform its operations.
+    Faulty,
+}
+
+/// Health response.
+///
+/// This response provides the health status of a device and the time elapsed
+/// since its boot.
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct HealthResponse {
+    /// Health status.
+    pub status: HealthStatus,
+    /// Time elapsed since the device boot, expressed in seconds.
+    pub uptime: u64,
+}
+
+impl HealthResponse {
+    /// Creates a [`HealthResponse`] for a device which works correctly.
+    #[must_use]
+    pub const fn new(uptime: u64) -> Self {
+        Self {
+            status: HealthStatus::Ok,
+            uptime,
+        }
+    }
+
+    /// Sets the [`HealthStatus`].
+    #[must_use]
+    pub const fn with_status(mut self, status: HealthStatus) -> Self {
+        self.status = status;
+        self
+    }
+}
+
+// Categories of a hazards summary, in serialization order.
+const CATEGORIES: [Category; 3] = [Category::Safety, Category::Financial, Category::Privacy];
+
+/// Hazards response.
+///
+/// This response summarizes the [`Hazard`]s of all device routes, grouped
+/// by [`Category`]. Each hazard is reported once, even when it is associated
+/// with more routes, and every category is always present, so a controller
+/// can rely on the response structure.
+#[derive(Debug)]
+pub struct HazardsResponse<'a, const H: usize, const P: usize, const N: usize, B: Backing = Hashed>
+{
+    route_configs: &'a RouteConfigs<H, P, N, B>,
+}
+
+impl<'a, const H: usize, const P: usize, const N: usize, B: Backing>
+    HazardsResponse<'a, H, P, N, B>
+{
+    /// Creates a [`HazardsResponse`] from the [`RouteConfigs`] of a device.
+    #[must_use]
+    pub const fn new(route_configs: &'a RouteConfigs<H, P, N, B>) -> Self {
+        Self { route_configs }
+    }
+
+    /// Returns an iterator over the distinct [`Hazard`]s of a [`Category`].
+    pub fn hazards(&self, category: Category) -> impl Iterator<Item = Hazard> + '_ {
+        let hazards = self
+            .route_configs
+            .iter()
+            .flat_map(|route_config| route_config.hazards().iter().copied());
+        hazards
+            .clone()
+            .enumerate()
+            .filter(move |(index, hazard)| {
+                hazard.category() == category
+                    && !hazards.clone().take(*index).any(|other| other == *hazard)
+            })
+            .map(|(_, hazard)| hazard)
+    }
+}
+
+// Hazards of a category.
+struct CategoryHazards<'a, 'b, const H: usize, const P: usize, const N: usize, B: Backing> {
+    response: &'b HazardsResponse<'a, H, P, N, B>,
+    category: Category,
+}
+
+impl<const H: usize, const P: usize, const N: usize, B: Backing> Serialize
+    for CategoryHazards<'_, '_, H, P, N, B>
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(None)?;
+        for hazard in self.response.hazards(self.category) {
+            seq.serialize_element(&hazard)?;
+        }
+        seq.end()
+    }
+}
+
+impl<const H: usize, const P: usize, const N: usize, B: Backing> Serialize
+    for HazardsResponse<'_, H, P, N, B>
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(CATEGORIES.len()))?;
+        for category in CATEGORIES {
+            map.serialize_entry(
+                &category,
+                &CategoryHazards {
+                    response: self,
+                    category,
+                },
+            )?;
+        }
+        map.end()
+    }
+}
+
 /// A response containing structured information about an error occurred during
 /// the execution of an action.
 ///
 /// It describes the kind of error, the cause, and optional information.
+/// It might also contain a numeric code, the route and the parameter which
+/// caused the error, and a hint about when to retry an action.
+///
+/// The optional fields are not serialized when missing, so a response
+/// without them is identical to a response of the `tosca` crate.
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
 pub struct ErrorResponse<const N: usize> {
     /// Action error type.
@@ -34,43 +182,125 @@ pub struct ErrorResponse<const N: usize> {
     pub description: String<N>,
     /// Information about an error.
     pub info: Option<String<N>>,
+    /// Machine-readable error code.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub code: Option<u16>,
+    /// Route which caused the error.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub route: Option<String<N>>,
+    /// Route parameter which caused the error.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub parameter: Option<String<N>>,
+    /// Number of seconds to wait before retrying an action
+    /// which failed because of a transient fault.
+    #[serde(rename = "retry after")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(default)]
+    pub retry_after: Option<u32>,
+}
+
+impl<const N: usize> From<Error> for ErrorResponse<N> {
+    fn from(error: Error) -> Self {
+        Self::from_error(&error)
+    }
 }
 
 impl<const N: usize> ErrorResponse<N> {
     /// Creates an [`ErrorResponse`] with a specific [`ErrorKind`] and
     /// a description.
     ///
-    /// If the `description` field is returned empty, it means that an error
-    /// occurred while creating its value.
+    /// A description greater than `N` bytes is truncated and terminated
+    /// with an [`ELLIPSIS`](crate::string::ELLIPSIS).
     #[must_use]
     #[inline]
     pub fn with_description(error: ErrorKind, description: &str) -> Self {
         Self {
             error,
-            description: String::infallible(description),
+            description: String::truncated_with_ellipsis(description),
             info: None,
+            code: None,
+            route: None,
+            parameter: None,
+            retry_after: None,
         }
     }
 
     /// Creates an [`ErrorResponse`] with a specific [`ErrorKind`], an
     /// error description, and additional information about the error.
     ///
-    /// If `description` and `information` fields are returned empty, it means
-    /// that an error occurred while creating their values.
+    /// A description or an information greater than `N` bytes is truncated
+    /// and terminated with an [`ELLIPSIS`](crate::string::ELLIPSIS).
     #[must_use]
     #[inline]
     pub fn with_description_error(error: ErrorKind, description: &str, info: &str) -> Self {
         Self {
-            error,
-            description: String::infallible(description),
-            info: Some(String::infallible(info)),
+            info: Some(String::truncated_with_ellipsis(info)),
+            ..Self::with_description(error, description)
         }
     }
 
+    /// Creates an [`ErrorResponse`] from an [`Error`].
+    ///
+    /// An [`Error`] caused by a route parameter, by a request
+    /// authentication, by a route authorization, or by a route rate limit is
+    /// reported as invalid data, while any other [`Error`] is reported as an
+    /// internal error.
+    /// The error code is the one of the [`Error`] kind.
+    #[must_use]
+    pub fn from_error(error: &Error) -> Self {
+        let kind = match error.kind() {
+            StackErrorKind::Parameter
+            | StackErrorKind::Authentication
+            | StackErrorKind::Authorization
+            | StackErrorKind::RateLimit => ErrorKind::InvalidData,
+            _ => ErrorKind::Internal,
+        };
+        Self::with_description_error(kind, error.kind().description(), error.info())
+            .code(error.kind().code())
+    }
+
+    /// Sets the error code.
+    #[must_use]
+    pub const fn code(mut self, code: u16) -> Self {
+        self.code = Some(code);
+        self
+    }
+
+    /// Sets the route which caused the error.
+    ///
+    /// A route greater than `N` bytes is truncated and terminated with an
+    /// [`ELLIPSIS`](crate::string::ELLIPSIS).
+    #[must_use]
+    #[inline]
+    pub fn route(mut self, route: &str) -> Self {
+        self.route = Some(String::truncated_with_ellipsis(route));
+        self
+    }
+
+    /// Sets the route parameter which caused the error.
+    ///
+    /// A parameter greater than `N` bytes is truncated and terminated with
+    /// an [`ELLIPSIS`](crate::string::ELLIPSIS).
+    #[must_use]
+    #[inline]
+    pub fn parameter(mut self, parameter: &str) -> Self {
+        self.parameter = Some(String::truncated_with_ellipsis(parameter));
+        self
+    }
+
+    /// Sets the number of seconds to wait before retrying the action.
+    #[must_use]
+    pub const fn retry_after(mut self, seconds: u32) -> Self {
+        self.retry_after = Some(seconds);
+        self
+    }
+
     /// Creates an [`ErrorResponse`] for invalid data with a description.
     ///
-    /// If the `description` field is returned empty, it means that an error
-    /// occurred while creating its value.
+    /// A description greater than `N` bytes is truncated.
     #[must_use]
     #[inline]
     pub fn invalid_data(description: &str) -> Self {
@@ -80,8 +310,7 @@ impl<const N: usize> ErrorResponse<N> {
     /// Creates an [`ErrorResponse`] for invalid data with a description and
     /// additional information about the error.
     ///
-    /// If `description` and `information` fields are returned empty, it means
-    /// that an error occurred while creating their values.
+    /// A description or an information greater than `N` bytes is truncated.
     #[must_use]
     #[inline]
     pub fn invalid_data_with_error(description: &str, info: &str) -> Self {
@@ -90,8 +319,7 @@ impl<const N: usize> ErrorResponse<N> {
 
     /// Creates an [`ErrorResponse`] for an internal error with a description.
     ///
-    /// If the `description` field is returned empty, it means that an error
-    /// occurred while creating its value.
+    /// A description greater than `N` bytes is truncated.
     #[must_use]
     #[inline]
     pub fn internal(description: &str) -> Self {
@@ -101,8 +329,7 @@ impl<const N: usize> ErrorResponse<N> {
     /// Creates an [`ErrorResponse`] for an internal error with a description
     /// and additional information about the error.
     ///
-    /// If `description` and `information` fields are returned empty, it means
-    /// that an error occurred while creating their values.
+    /// A description or an information greater than `N` bytes is truncated.
     #[must_use]
     #[inline]
     pub fn internal_with_error(description: &str, info: &str) -> Self {
@@ -112,6 +339,9 @@ impl<const N: usize> ErrorResponse<N> {
 
 #[cfg(test)]
 mod tests {
+    use serde_json::json;
+
+    use crate::error::{Error, ErrorKind as StackErrorKind};
     use crate::{deserialize, serialize};
 
     use super::{ErrorKind, ErrorResponse, String};
@@ -131,7 +361,74 @@ mod tests {
                 error: ErrorKind::InvalidData,
                 description: String::infallible("Invalid data error description"),
                 info: None,
+                code: None,
+                route: None,
+                parameter: None,
+                retry_after: None,
             }
         );
     }
+
+    #[test]
+    fn test_truncated_error_response() {
+        let error = ErrorResponse::<16>::invalid_data_with_error(
+            "Invalid brightness value",
+            "Out of range",
+        );
+
+        assert_eq!(error.description, "Invalid brigh...");
+        assert_eq!(error.info.unwrap(), "Out of range");
+    }
+
+    #[test]
+    fn test_error_response_context() {
+        let error = ErrorResponse::<STRING_SIZE>::internal("Device busy")
+            .code(42)
+            .route("/light/on")
+            .parameter("brightness")
+            .retry_after(5);
+
+        let value = serialize(&error);
+        assert_eq!(
+            value,
+            json!({
+                "error": "Internal",
+                "description": "Device busy",
+                "info": null,
+                "code": 42,
+                "route": "/light/on",
+                "parameter": "brightness",
+                "retry after": 5
+            })
+        );
+        assert_eq!(deserialize::<ErrorResponse<STRING_SIZE>>(value), error);
+
+        // A response without the optional fields is still accepted.
+        assert_eq!(
+            deserialize::<ErrorResponse<STRING_SIZE>>(json!({
+                "error": "Internal",
+                "description": "Device busy",
+                "info": null
+            })),
+            ErrorResponse::internal("Device busy")
+        );
+    }
+
+    #[test]
+    fn test_error_response_from_error() {
+        let error = ErrorResponse::<STRING_SIZE>::from(Error::new(
+            StackErrorKind::Parameter,
+            "Out of range",
+        ));
+
+        assert_eq!(error.error, ErrorKind::InvalidData);
+        assert_eq!(error.description, "Route parameter");
+        assert_eq!(error.info.unwrap(), "Out of range");
+        assert_eq!(error.code, Some(StackErrorKind::Parameter.code()));
+
+        let error =
+            ErrorResponse::<STRING_SIZE>::from(Error::new(StackErrorKind::Capacity, "Full"));
+        assert_eq!(error.error, ErrorKind::Internal);
+        assert_eq!(error.code, Some(StackErrorKind::Capacity.code()));
+    }
 }
@@ -2,52 +2,110 @@ use core::hash::{Hash, Hasher};
 
 use tosca::response::ResponseKind;
 
-use serde::Serialize;
+use serde::ser::SerializeMap;
+use serde::{Serialize, Serializer};
 
-use crate::collections::{SerialSet, Set};
-use crate::hazards::Hazards;
-use crate::parameters::{Parameters, ParametersData};
+use crate::access::{self, AccessLevel};
+use crate::collections::{Backing, Hashed, SerialSet, Set};
+use crate::description::Descriptions;
+use crate::error::{Error, ErrorKind, Result};
+use crate::hazards::{Hazard, Hazards};
+use crate::parameters::{
+    ParameterDescriptor, Parameters, ParametersMap, ParametersMapDescriptions, ParametersTable,
+    ParametersTableDescriptions,
+};
+use crate::rate_limit::RateLimit;
 
 pub use tosca::route::RestKind;
 
 /// Route data.
-#[derive(Debug, Clone, Serialize)]
-pub struct RouteData<const H: usize, const P: usize> {
-    /// Name.
+#[derive(Debug, Clone)]
+pub struct RouteData<const H: usize, const P: usize, B: Backing = Hashed> {
+    // Name.
     name: &'static str,
-    /// Description.
+    // Description.
     description: Option<&'static str>,
-    /// Hazards data.
-    #[serde(skip_serializing_if = "Hazards::is_empty")]
-    hazards: Hazards<H>,
-    /// Input parameters associated with a route..
-    #[serde(skip_serializing_if = "ParametersData::is_empty")]
-    parameters: ParametersData<P>,
+    // Localized descriptions.
+    descriptions: Descriptions,
+    // Hazards data.
+    hazards: Hazards<H, B>,
+    // Input parameters associated with a route.
+    parameters: Parameters<P, B>,
+    // Estimated energy consumed by an invocation.
+    energy: Option<f32>,
+    // Estimated cost of an invocation.
+    cost: Option<f32>,
+    // Access level required to invoke a route.
+    access_level: Option<AccessLevel>,
+    // Rate limit of a route.
+    rate_limit: Option<RateLimit>,
+}
+
+impl<const H: usize, const P: usize, B: Backing> Serialize for RouteData<H, P, B> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry("name", self.name)?;
+        map.serialize_entry("path", self.name)?;
+        map.serialize_entry("description", &self.description)?;
+        if !self.descriptions.is_empty() {
+            map.serialize_entry("descriptions", &self.descriptions)?;
+        }
+        if !self.hazards.is_empty() {
+            map.serialize_entry("hazards", &self.hazards)?;
+        }
+        let kinds = ParametersMap(&self.parameters);
+        if !kinds.is_empty() {
+            map.serialize_entry("parameters", &kinds)?;
+        }
+        let descriptions = ParametersMapDescriptions(&self.parameters);
+        if !descriptions.is_empty() {
+            map.serialize_entry("parameters descriptions", &descriptions)?;
+        }
+        if let Some(energy) = self.energy {
+            map.serialize_entry("energy per invocation", &energy)?;
+        }
+        if let Some(cost) = self.cost {
+            map.serialize_entry("cost per invocation", &cost)?;
+        }
+        if let Some(access_level) = self.access_level {
+            map.serialize_entry("access level", &access_level)?;
+        }
+        if let Some(rate_limit) = &self.rate_limit {
+            map.serialize_entry("rate limit", rate_limit)?;
+        }
+        map.end()
+    }
 }
 
-impl<const H: usize, const P: usize> PartialEq for RouteData<H, P> {
+impl<const H: usize, const P: usize, B: Backing> PartialEq for RouteData<H, P, B> {
     fn eq(&self, other: &Self) -> bool {
         self.name.eq(other.name)
     }
 }
 
-impl<const H: usize, const P: usize> RouteData<H, P> {
-    fn new(route: Route<H, P>) -> Self {
+impl<const H: usize, const P: usize, B: Backing> RouteData<H, P, B> {
+    fn new(route: Route<H, P, B>) -> Self {
         Self {
             name: route.name,
             description: route.description,
+            descriptions: route.descriptions,
             hazards: route.hazards,
-            parameters: route.parameters.serialize_data(),
+            parameters: route.parameters,
+            energy: route.energy,
+            cost: route.cost,
+            access_level: route.access_level,
+            rate_limit: route.rate_limit,
         }
     }
 }
 
 /// A server route configuration.
 #[derive(Debug, Clone, Serialize)]
-pub struct RouteConfig<const H: usize, const P: usize> {
+#[serde(bound = "")]
+pub struct RouteConfig<const H: usize, const P: usize, B: Backing = Hashed> {
     /// Route.
     #[serde(flatten)]
-    data: RouteData<H, P>,
+    data: RouteData<H, P, B>,
     /// **_REST_** kind..
     #[serde(rename = "REST kind")]
     rest_kind: RestKind,
@@ -56,64 +114,358 @@ pub struct RouteConfig<const H: usize, const P: usize> {
     response_kind: ResponseKind,
 }
 
-impl<const H: usize, const P: usize> PartialEq for RouteConfig<H, P> {
+impl<const H: usize, const P: usize, B: Backing> PartialEq for RouteConfig<H, P, B> {
     fn eq(&self, other: &Self) -> bool {
         self.data.eq(&other.data) && self.rest_kind == other.rest_kind
     }
 }
 
 // Hazards and inputs prevent Eq trait to be derived.
-impl<const H: usize, const P: usize> Eq for RouteConfig<H, P> {}
+impl<const H: usize, const P: usize, B: Backing> Eq for RouteConfig<H, P, B> {}
 
-impl<const H: usize, const P: usize> Hash for RouteConfig<H, P> {
+impl<const H: usize, const P: usize, B: Backing> Hash for RouteConfig<H, P, B> {
     fn hash<Ha: Hasher>(&self, state: &mut Ha) {
         self.data.name.hash(state);
         self.rest_kind.hash(state);
     }
 }
 
-impl<const H: usize, const P: usize> RouteConfig<H, P> {
-    fn new(route: Route<H, P>) -> Self {
+impl<const H: usize, const P: usize, B: Backing> RouteConfig<H, P, B> {
+    fn new(route: Route<H, P, B>) -> Self {
         Self {
             rest_kind: route.rest_kind,
             response_kind: ResponseKind::default(),
             data: RouteData::new(route),
         }
     }
+
+    /// Returns route.
+    #[must_use]
+    pub const fn route(&self) -> &'static str {
+        self.data.name
+    }
+
+    /// Returns [`RestKind`].
+    #[must_use]
+    pub const fn kind(&self) -> RestKind {
+        self.rest_kind
+    }
+
+    /// Returns [`Hazards`].
+    #[must_use]
+    pub const fn hazards(&self) -> &Hazards<H, B> {
+        &self.data.hazards
+    }
+
+    /// Returns [`ResponseKind`].
+    #[must_use]
+    pub const fn response_kind(&self) -> ResponseKind {
+        self.response_kind
+    }
+
+    /// Returns the [`AccessLevel`] required to invoke the route.
+    ///
+    /// A route without a declared access level can be invoked by any caller.
+    #[must_use]
+    pub const fn access_level(&self) -> AccessLevel {
+        match self.data.access_level {
+            Some(access_level) => access_level,
+            None => AccessLevel::Guest,
+        }
+    }
+
+    /// Checks whether a caller with the given [`AccessLevel`] can invoke the
+    /// route.
+    ///
+    /// # Errors
+    ///
+    /// If the caller access level is lower than the required one, an error
+    /// is returned.
+    pub fn authorize(&self, caller: AccessLevel) -> Result<()> {
+        access::authorize(self.access_level(), caller)
+    }
+
+    /// Returns the [`RateLimit`] of the route.
+    #[must_use]
+    pub const fn rate_limit(&self) -> Option<RateLimit> {
+        self.data.rate_limit
+    }
+
+    // Sets the response kind.
+    pub(crate) const fn with_response_kind(mut self, response_kind: ResponseKind) -> Self {
+        self.response_kind = response_kind;
+        self
+    }
 }
 
 /// A collection of [`RouteConfig`]s.
-pub type RouteConfigs<const H: usize, const P: usize, const N: usize> =
-    SerialSet<RouteConfig<H, P>, N>;
+pub type RouteConfigs<const H: usize, const P: usize, const N: usize, B = Hashed> =
+    SerialSet<RouteConfig<H, P, B>, N, B>;
+
+/// A warning about a route path defined by more than one [`Route`]
+/// with different [`Hazards`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RouteWarning {
+    /// Route.
+    pub route: &'static str,
+    /// **_REST_** kind of the route which raised the warning.
+    pub rest_kind: RestKind,
+    /// **_REST_** kind of the route previously defined with the same path.
+    pub conflicting_rest_kind: RestKind,
+}
+
+/// A [`RouteConfigs`] builder which validates each [`Route`].
+///
+/// A route is rejected when its name does not start with `/`, when it
+/// contains characters not allowed in a path, or when a route with the same
+/// name and [`RestKind`] has already been added.
+///
+/// When a path is defined with different [`RestKind`]s and different
+/// [`Hazards`], a [`RouteWarning`] is recorded. Up to `N` warnings are
+/// recorded, and a route raising further warnings is rejected.
+#[derive(Debug)]
+pub struct RouteConfigsBuilder<const H: usize, const P: usize, const N: usize, B: Backing = Hashed>
+{
+    route_configs: RouteConfigs<H, P, N, B>,
+    warnings: Set<RouteWarning, N, B>,
+}
+
+impl<const H: usize, const P: usize, const N: usize, B: Backing> Default
+    for RouteConfigsBuilder<H, P, N, B>
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const H: usize, const P: usize, const N: usize, B: Backing> RouteConfigsBuilder<H, P, N, B> {
+    /// Creates a [`RouteConfigsBuilder`].
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            route_configs: RouteConfigs::new(),
+            warnings: Set::new(),
+        }
+    }
+
+    /// Adds a [`Route`].
+    ///
+    /// # Errors
+    ///
+    /// If the route is not valid, if it is a duplicate, or if either the
+    /// [`RouteConfigs`] or the [`RouteWarning`]s capacity has been reached,
+    /// an error is returned.
+    pub fn route(mut self, route: Route<H, P, B>) -> Result<Self> {
+        self.add(route)?;
+        Ok(self)
+    }
+
+    /// Adds a [`Route`].
+    ///
+    /// # Errors
+    ///
+    /// If the route is not valid, if it is a duplicate, or if either the
+    /// [`RouteConfigs`] or the [`RouteWarning`]s capacity has been reached,
+    /// an error is returned.
+    pub fn add(&mut self, route: Route<H, P, B>) -> Result<()> {
+        check_route(&self.route_configs, route.name, route.rest_kind)?;
+
+        if self.route_configs.len() == N {
+            return Err(full_route_configs());
+        }
+
+        let conflicts = self.route_configs.iter().filter(|route_config| {
+            route_config.data.name == route.name && route_config.data.hazards != route.hazards
+        });
+
+        if self.warnings.len() + conflicts.clone().count() > N {
+            return Err(Error::new(
+                ErrorKind::Capacity,
+                "Impossible to record another route warning, the warnings are full.",
+            ));
+        }
+
+        for route_config in conflicts {
+            self.warnings.add(RouteWarning {
+                route: route.name,
+                rest_kind: route.rest_kind,
+                conflicting_rest_kind: route_config.rest_kind,
+            });
+        }
+
+        self.route_configs.add(route.serialize_data());
+        Ok(())
+    }
+
+    /// Returns the [`RouteWarning`]s recorded while adding routes.
+    #[must_use]
+    pub const fn warnings(&self) -> &Set<RouteWarning, N, B> {
+        &self.warnings
+    }
+
+    /// Builds [`RouteConfigs`].
+    #[must_use]
+    pub fn build(self) -> RouteConfigs<H, P, N, B> {
+        self.route_configs
+    }
+}
+
+/// Checks a list of routes, each one defined by its name and [`RestKind`].
+///
+/// It runs the same checks of [`RouteConfigsBuilder`] in a constant
+/// context, so the `device!` macro rejects invalid routes at compile time.
+///
+/// # Errors
+///
+/// If a route is not valid or if it is a duplicate, an error is returned.
+#[doc(hidden)]
+pub const fn check_routes(routes: &[(&str, RestKind)]) -> Result<()> {
+    let mut index = 0;
+    while index < routes.len() {
+        let (name, rest_kind) = routes[index];
+        if let Err(error) = check_route_name(name) {
+            return Err(error);
+        }
+
+        let mut other = 0;
+        while other < index {
+            if is_duplicate(routes[other], (name, rest_kind)) {
+                return Err(duplicate_route());
+            }
+            other += 1;
+        }
+        index += 1;
+    }
+
+    Ok(())
+}
+
+// A route name must start with `/` and contain only unreserved URI
+// characters and `/` separators.
+const fn check_route_name(name: &str) -> Result<()> {
+    let bytes = name.as_bytes();
+    if bytes.is_empty() || bytes[0] != b'/' {
+        return Err(Error::new(
+            ErrorKind::Route,
+            "A route name must start with `/`.",
+        ));
+    }
+
+    let mut index = 0;
+    while index < bytes.len() {
+        let c = bytes[index];
+        if !(c.is_ascii_alphanumeric() || matches!(c, b'/' | b'-' | b'.' | b'_' | b'~')) {
+            return Err(Error::new(
+                ErrorKind::Route,
+                "A route name contains illegal characters.",
+            ));
+        }
+        index += 1;
+    }
+
+    Ok(())
+}
+
+// Two routes are duplicates when they have the same name and REST kind.
+const fn is_duplicate(route: (&str, RestKind), other: (&str, RestKind)) -> bool {
+    route.1 as u8 == other.1 as u8 && is_same_name(route.0, other.0)
+}
+
+const fn is_same_name(name: &str, other: &str) -> bool {
+    let (name, other) = (name.as_bytes(), other.as_bytes());
+    if name.len() != other.len() {
+        return false;
+    }
+
+    let mut index = 0;
+    while index < name.len() {
+        if name[index] != other[index] {
+            return false;
+        }
+        index += 1;
+    }
+
+    true
+}
+
+pub(crate) const fn duplicate_route() -> Error {
+    Error::new(
+        ErrorKind::DuplicateRoute,
+        "A route with the same name and REST kind already exists.",
+    )
+}
+
+pub(crate) const fn full_route_configs() -> Error {
+    Error::new(
+        ErrorKind::Capacity,
+        "Impossible to add another route, the route configurations are full.",
+    )
+}
+
+// Checks whether a route, defined by its name and `RestKind`, is valid and
+// is not a duplicate of any route configuration.
+pub(crate) fn check_route<const H: usize, const P: usize, const N: usize, B: Backing>(
+    route_configs: &RouteConfigs<H, P, N, B>,
+    name: &str,
+    rest_kind: RestKind,
+) -> Result<()> {
+    check_route_name(name)?;
+    if route_configs.iter().any(|route_config| {
+        is_duplicate(
+            (route_config.data.name, route_config.rest_kind),
+            (name, rest_kind),
+        )
+    }) {
+        return Err(duplicate_route());
+    }
+    Ok(())
+}
 
 /// A server route.
 ///
 /// It represents a specific `REST` API which, when invoked, runs a task on
 /// a remote device.
-#[derive(Debug)]
-pub struct Route<const H: usize, const P: usize> {
+///
+/// A new route has [`Hazards`] and [`Parameters`] capacities of two, which
+/// are changed when [`Hazards`] and [`Parameters`] are added.
+///
+/// Its [`Backing`] is [`Hashed`] by default. With a
+/// [`Linear`](crate::collections::Linear) backing, hazards, parameters and
+/// routes accept any capacity, zero included.
+#[derive(Debug, Clone)]
+pub struct Route<const H: usize, const P: usize, B: Backing = Hashed> {
     // Route.
     name: &'static str,
     // REST kind.
     rest_kind: RestKind,
     // Description.
     description: Option<&'static str>,
+    // Localized descriptions.
+    descriptions: Descriptions,
     // Input route parameters.
-    parameters: Parameters<P>,
+    parameters: Parameters<P, B>,
     // Hazards.
-    hazards: Hazards<H>,
+    hazards: Hazards<H, B>,
+    // Estimated energy consumed by an invocation.
+    energy: Option<f32>,
+    // Estimated cost of an invocation.
+    cost: Option<f32>,
+    // Access level required to invoke a route.
+    access_level: Option<AccessLevel>,
+    // Rate limit of a route.
+    rate_limit: Option<RateLimit>,
 }
 
-impl<const H: usize, const P: usize> PartialEq for Route<H, P> {
+impl<const H: usize, const P: usize, B: Backing> PartialEq for Route<H, P, B> {
     fn eq(&self, other: &Self) -> bool {
         self.name == other.name && self.rest_kind == other.rest_kind
     }
 }
 
 // Hazards and inputs prevent Eq trait to be derived.
-impl<const H: usize, const P: usize> Eq for Route<H, P> {}
+impl<const H: usize, const P: usize, B: Backing> Eq for Route<H, P, B> {}
 
-impl<const H: usize, const P: usize> Hash for Route<H, P> {
+impl<const H: usize, const P: usize, B: Backing> Hash for Route<H, P, B> {
     fn hash<Ha: Hasher>(&self, state: &mut Ha) {
         self.name.hash(state);
         self.rest_kind.hash(state);
@@ -121,43 +473,50 @@ impl<const H: usize, const P: usize> Hash for Route<H, P> {
     }
 }
 
-impl Route<2, 2> {
+impl<B: Backing> Route<2, 2, B> {
     /// Creates a new [`Route`] through a REST `GET` API.
     #[must_use]
-    pub fn get(route: &'static str) -> Self {
+    pub const fn get(route: &'static str) -> Self {
         Self::init(RestKind::Get, route)
     }
 
     /// Creates a new [`Route`] through a REST `PUT` API.
     #[must_use]
-    pub fn put(route: &'static str) -> Self {
+    pub const fn put(route: &'static str) -> Self {
         Self::init(RestKind::Put, route)
     }
 
     /// Creates a new [`Route`] through a REST `POST` API.
     #[must_use]
-    pub fn post(route: &'static str) -> Self {
+    pub const fn post(route: &'static str) -> Self {
         Self::init(RestKind::Post, route)
     }
 
     /// Creates a new [`Route`] through a REST `DELETE` API.
     #[must_use]
-    pub fn delete(route: &'static str) -> Self {
+    pub const fn delete(route: &'static str) -> Self {
         Self::init(RestKind::Delete, route)
     }
+}
 
-    fn init(rest_kind: RestKind, route: &'static str) -> Self {
-        Route::<2, 2> {
+impl<const H: usize, const P: usize, B: Backing> Route<H, P, B> {
+    // Creates a route with empty hazards and parameters, whose capacities
+    // are inferred from the collection of routes it is added to.
+    pub(crate) const fn init(rest_kind: RestKind, route: &'static str) -> Self {
+        Self {
             name: route,
             rest_kind,
             description: None,
+            descriptions: Descriptions::empty(),
             parameters: Parameters::new(),
             hazards: Hazards::new(),
+            energy: None,
+            cost: None,
+            access_level: None,
+            rate_limit: None,
         }
     }
-}
 
-impl<const H: usize, const P: usize> Route<H, P> {
     /// Sets the route description.
     #[must_use]
     pub const fn description(mut self, description: &'static str) -> Self {
@@ -165,6 +524,47 @@ impl<const H: usize, const P: usize> Route<H, P> {
         self
     }
 
+    /// Sets the route localized descriptions.
+    ///
+    /// Each entry associates a language tag with a description text.
+    #[must_use]
+    pub const fn localized_descriptions(
+        mut self,
+        descriptions: &'static [(&'static str, &'static str)],
+    ) -> Self {
+        self.descriptions = Descriptions::new(descriptions);
+        self
+    }
+
+    /// Sets the estimated energy consumed by an invocation, expressed in
+    /// watt-hours (Wh).
+    #[must_use]
+    pub const fn with_energy(mut self, energy: f32) -> Self {
+        self.energy = Some(energy);
+        self
+    }
+
+    /// Sets the estimated cost of an invocation, expressed in USD.
+    #[must_use]
+    pub const fn with_cost(mut self, cost: f32) -> Self {
+        self.cost = Some(cost);
+        self
+    }
+
+    /// Sets the [`AccessLevel`] required to invoke the route.
+    #[must_use]
+    pub const fn with_access_level(mut self, access_level: AccessLevel) -> Self {
+        self.access_level = Some(access_level);
+        self
+    }
+
+    /// Sets the [`RateLimit`] of the route.
+    #[must_use]
+    pub const fn with_rate_limit(mut self, rate_limit: RateLimit) -> Self {
+        self.rate_limit = Some(rate_limit);
+        self
+    }
+
     /// Changes the route.
     #[must_use]
     pub const fn change_route(mut self, route: &'static str) -> Self {
@@ -175,32 +575,45 @@ impl<const H: usize, const P: usize> Route<H, P> {
     /// Adds [`Hazards`] to a [`Route`].
     #[must_use]
     #[inline]
-    pub fn with_hazards<const H2: usize>(self, hazards: Hazards<H2>) -> Route<H2, P> {
-        Route::<H2, P> {
+    pub fn with_hazards<const H2: usize>(self, hazards: Hazards<H2, B>) -> Route<H2, P, B> {
+        Route::<H2, P, B> {
             name: self.name,
             rest_kind: self.rest_kind,
             description: self.description,
+            descriptions: self.descriptions,
             parameters: self.parameters,
             hazards,
+            energy: self.energy,
+            cost: self.cost,
+            access_level: self.access_level,
+            rate_limit: self.rate_limit,
         }
     }
 
     /// Adds [`Parameters`] to a [`Route`].
     #[must_use]
     #[inline]
-    pub fn with_parameters<const P2: usize>(self, parameters: Parameters<P2>) -> Route<H, P2> {
-        Route::<H, P2> {
+    pub fn with_parameters<const P2: usize>(
+        self,
+        parameters: Parameters<P2, B>,
+    ) -> Route<H, P2, B> {
+        Route::<H, P2, B> {
             name: self.name,
             rest_kind: self.rest_kind,
             description: self.description,
+            descriptions: self.descriptions,
             parameters,
             hazards: self.hazards,
+            energy: self.energy,
+            cost: self.cost,
+            access_level: self.access_level,
+            rate_limit: self.rate_limit,
         }
     }
 
     /// Returns route.
     #[must_use]
-    pub const fn route(&self) -> &str {
+    pub const fn route(&self) -> &'static str {
         self.name
     }
 
@@ -210,53 +623,395 @@ impl<const H: usize, const P: usize> Route<H, P> {
         self.rest_kind
     }
 
+    /// Returns the route [`Descriptions`].
+    #[must_use]
+    pub const fn descriptions(&self) -> &Descriptions {
+        &self.descriptions
+    }
+
     /// Returns [`Hazards`].
     #[must_use]
-    pub const fn hazards(&self) -> &Hazards<H> {
+    pub const fn hazards(&self) -> &Hazards<H, B> {
         &self.hazards
     }
 
     /// Returns [`Parameters`].
     #[must_use]
-    pub const fn parameters(&self) -> &Parameters<P> {
+    pub const fn parameters(&self) -> &Parameters<P, B> {
         &self.parameters
     }
 
+    /// Returns the estimated energy consumed by an invocation, expressed in
+    /// watt-hours (Wh).
+    #[must_use]
+    pub const fn energy(&self) -> Option<f32> {
+        self.energy
+    }
+
+    /// Returns the estimated cost of an invocation, expressed in USD.
+    #[must_use]
+    pub const fn cost(&self) -> Option<f32> {
+        self.cost
+    }
+
+    /// Returns the [`AccessLevel`] required to invoke the route.
+    ///
+    /// A route without a declared access level can be invoked by any caller.
+    #[must_use]
+    pub const fn access_level(&self) -> AccessLevel {
+        match self.access_level {
+            Some(access_level) => access_level,
+            None => AccessLevel::Guest,
+        }
+    }
+
+    /// Checks whether a caller with the given [`AccessLevel`] can invoke the
+    /// route.
+    ///
+    /// # Errors
+    ///
+    /// If the caller access level is lower than the required one, an error
+    /// is returned.
+    pub fn authorize(&self, caller: AccessLevel) -> Result<()> {
+        access::authorize(self.access_level(), caller)
+    }
+
+    /// Returns the [`RateLimit`] of the route.
+    #[must_use]
+    pub const fn rate_limit(&self) -> Option<RateLimit> {
+        self.rate_limit
+    }
+
     /// Serializes [`Route`] data.
     ///
     /// It consumes the data.
     #[must_use]
     #[inline]
-    pub fn serialize_data(self) -> RouteConfig<H, P> {
+    pub fn serialize_data(self) -> RouteConfig<H, P, B> {
         RouteConfig::new(self)
     }
 }
 
 /// A collection of [`Route`]s.
 ///
-/// **For alignment reasons, it accepts only a power of two
-/// as number of elements.**
-pub type Routes<const H: usize, const P: usize, const N: usize> = Set<Route<H, P>, N>;
+/// **For alignment reasons, with the default [`Hashed`] backing it accepts
+/// only a power of two as number of elements.**
+pub type Routes<const H: usize, const P: usize, const N: usize, B = Hashed> =
+    Set<Route<H, P, B>, N, B>;
+
+impl<const H: usize, const P: usize, const N: usize, B: Backing> Routes<H, P, N, B> {
+    /// Serializes [`Routes`] data into [`RouteConfigs`].
+    ///
+    /// Differently from [`Route::serialize_data`], it does not consume
+    /// the data, so [`Routes`] can still be used to run device operations.
+    #[must_use]
+    pub fn serialize_data(&self) -> RouteConfigs<H, P, N, B> {
+        let mut route_configs = RouteConfigs::new();
+        for route in self {
+            route_configs.add(route.clone().serialize_data());
+        }
+        route_configs
+    }
+}
+
+/// A server route which can be built in a `const` context.
+///
+/// Differently from [`Route`], its [`Hazard`]s and [`ParameterDescriptor`]s
+/// are static slices, so a whole route table can be defined as a `static`
+/// item and stored in read-only memory. A slice of [`RouteDescriptor`]s
+/// produces the same serialization of [`RouteConfigs`].
+#[derive(Debug, Clone, Copy)]
+pub struct RouteDescriptor {
+    // Route.
+    name: &'static str,
+    // REST kind.
+    rest_kind: RestKind,
+    // Description.
+    description: Option<&'static str>,
+    // Localized descriptions.
+    descriptions: Descriptions,
+    // Hazards.
+    hazards: &'static [Hazard],
+    // Input route parameters.
+    parameters: &'static [ParameterDescriptor],
+    // Estimated energy consumed by an invocation.
+    energy: Option<f32>,
+    // Estimated cost of an invocation.
+    cost: Option<f32>,
+    // Access level required to invoke a route.
+    access_level: Option<AccessLevel>,
+    // Rate limit of a route.
+    rate_limit: Option<RateLimit>,
+}
+
+impl Serialize for RouteDescriptor {
+    fn serialize<S: Serializer>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error> {
+        let parameters_descriptions = ParametersTableDescriptions(self.parameters);
+
+        let mut map = serializer.serialize_map(None)?;
+        map.serialize_entry("name", self.name)?;
+        map.serialize_entry("path", self.name)?;
+        map.serialize_entry("description", &self.description)?;
+        if !self.descriptions.is_empty() {
+            map.serialize_entry("descriptions", &self.descriptions)?;
+        }
+        if !self.hazards.is_empty() {
+            map.serialize_entry("hazards", self.hazards)?;
+        }
+        if !self.parameters.is_empty() {
+            map.serialize_entry("parameters", &ParametersTable(self.parameters))?;
+        }
+        if !parameters_descriptions.is_empty() {
+            map.serialize_entry("parameters descriptions", &parameters_descriptions)?;
+        }
+        if let Some(energy) = self.energy {
+            map.serialize_entry("energy per invocation", &energy)?;
+        }
+        if let Some(cost) = self.cost {
+            map.serialize_entry("cost per invocation", &cost)?;
+        }
+        if let Some(access_level) = self.access_level {
+            map.serialize_entry("access level", &access_level)?;
+        }
+        if let Some(rate_limit) = &self.rate_limit {
+            map.serialize_entry("rate limit", rate_limit)?;
+        }
+        map.serialize_entry("REST kind", &self.rest_kind)?;
+        map.serialize_entry("response kind", &ResponseKind::default())?;
+        map.end()
+    }
+}
+
+impl RouteDescriptor {
+    /// Creates a new [`RouteDescriptor`] through a REST `GET` API.
+    #[must_use]
+    pub const fn get(route: &'static str) -> Self {
+        Self::init(RestKind::Get, route)
+    }
+
+    /// Creates a new [`RouteDescriptor`] through a REST `PUT` API.
+    #[must_use]
+    pub const fn put(route: &'static str) -> Self {
+        Self::init(RestKind::Put, route)
+    }
+
+    /// Creates a new [`RouteDescriptor`] through a REST `POST` API.
+    #[must_use]
+    pub const fn post(route: &'static str) -> Self {
+        Self::init(RestKind::Post, route)
+    }
+
+    /// Creates a new [`RouteDescriptor`] through a REST `DELETE` API.
+    #[must_use]
+    pub const fn delete(route: &'static str) -> Self {
+        Self::init(RestKind::Delete, route)
+    }
+
+    /// Sets the route description.
+    #[must_use]
+    pub const fn description(mut self, description: &'static str) -> Self {
+        self.description = Some(description);
+        self
+    }
+
+    /// Sets the route localized descriptions.
+    ///
+    /// Each entry associates a language tag with a description text.
+    #[must_use]
+    pub const fn localized_descriptions(
+        mut self,
+        descriptions: &'static [(&'static str, &'static str)],
+    ) -> Self {
+        self.descriptions = Descriptions::new(descriptions);
+        self
+    }
+
+    /// Sets the route [`Hazard`]s.
+    ///
+    /// Hazards must be unique within the slice.
+    #[must_use]
+    pub const fn with_hazards(mut self, hazards: &'static [Hazard]) -> Self {
+        self.hazards = hazards;
+        self
+    }
+
+    /// Sets the route input parameters.
+    ///
+    /// Parameter names must be unique within the slice.
+    #[must_use]
+    pub const fn with_parameters(mut self, parameters: &'static [ParameterDescriptor]) -> Self {
+        self.parameters = parameters;
+        self
+    }
+
+    /// Sets the estimated energy consumed by an invocation, expressed in
+    /// watt-hours (Wh).
+    #[must_use]
+    pub const fn with_energy(mut self, energy: f32) -> Self {
+        self.energy = Some(energy);
+        self
+    }
+
+    /// Sets the estimated cost of an invocation, expressed in USD.
+    #[must_use]
+    pub const fn with_cost(mut self, cost: f32) -> Self {
+        self.cost = Some(cost);
+        self
+    }
+
+    /// Sets the [`AccessLevel`] required to invoke the route.
+    #[must_use]
+    pub const fn with_access_level(mut self, access_level: AccessLevel) -> Self {
+        self.access_level = Some(access_level);
+        self
+    }
+
+    /// Sets the [`RateLimit`] of the route.
+    #[must_use]
+    pub const fn with_rate_limit(mut self, rate_limit: RateLimit) -> Self {
+        self.rate_limit = Some(rate_limit);
+        self
+    }
+
+    /// Returns route.
+    #[must_use]
+    pub const fn route(&self) -> &'static str {
+        self.name
+    }
+
+    /// Returns [`RestKind`].
+    #[must_use]
+    pub const fn kind(&self) -> RestKind {
+        self.rest_kind
+    }
+
+    /// Returns the route [`Descriptions`].
+    #[must_use]
+    pub const fn descriptions(&self) -> &Descriptions {
+        &self.descriptions
+    }
+
+    /// Returns the route [`Hazard`]s.
+    #[must_use]
+    pub const fn hazards(&self) -> &'static [Hazard] {
+        self.hazards
+    }
+
+    /// Returns the route input parameters.
+    #[must_use]
+    pub const fn parameters(&self) -> &'static [ParameterDescriptor] {
+        self.parameters
+    }
+
+    /// Returns the input parameter with the given name.
+    #[must_use]
+    pub fn parameter(&self, name: &str) -> Option<&'static ParameterDescriptor> {
+        self.parameters
+            .iter()
+            .find(|parameter| parameter.name() == name)
+    }
+
+    /// Returns the estimated energy consumed by an invocation, expressed in
+    /// watt-hours (Wh).
+    #[must_use]
+    pub const fn energy(&self) -> Option<f32> {
+        self.energy
+    }
+
+    /// Returns the estimated cost of an invocation, expressed in USD.
+    #[must_use]
+    pub const fn cost(&self) -> Option<f32> {
+        self.cost
+    }
+
+    /// Returns the [`AccessLevel`] required to invoke the route.
+    ///
+    /// A route without a declared access level can be invoked by any caller.
+    #[must_use]
+    pub const fn access_level(&self) -> AccessLevel {
+        match self.access_level {
+            Some(access_level) => access_level,
+            None => AccessLevel::Guest,
+        }
+    }
+
+    /// Checks whether a caller with the given [`AccessLevel`] can invoke the
+    /// route.
+    ///
+    /// # Errors
+    ///
+    /// If the caller access level is lower than the required one, an error
+    /// is returned.
+    pub fn authorize(&self, caller: AccessLevel) -> Result<()> {
+        access::authorize(self.access_level(), caller)
+    }
+
+    /// Returns the [`RateLimit`] of the route.
+    #[must_use]
+    pub const fn rate_limit(&self) -> Option<RateLimit> {
+        self.rate_limit
+    }
+
+    const fn init(rest_kind: RestKind, route: &'static str) -> Self {
+        Self {
+            name: route,
+            rest_kind,
+            description: None,
+            descriptions: Descriptions::empty(),
+            hazards: &[],
+            parameters: &[],
+            energy: None,
+            cost: None,
+            access_level: None,
+            rate_limit: None,
+        }
+    }
+}
 
 #[cfg(test)]
 mod tests {
     use serde_json::json;
     use tosca::hazards::Hazard;
 
+    use crate::access::AccessLevel;
+    use crate::collections::Linear;
+    use crate::error::ErrorKind;
+    use crate::rate_limit::RateLimit;
     use crate::serialize;
 
-    use super::{Hazards, Parameters, Route};
+    use super::{
+        Hazards, ParameterDescriptor, Parameters, RestKind, Route, RouteConfigsBuilder,
+        RouteDescriptor, RouteWarning, check_routes,
+    };
+
+    static ROUTES: &[RouteDescriptor] = &[
+        RouteDescriptor::put("/on")
+            .description("Turns the light on")
+            .localized_descriptions(&[("en", "Turns the light on"), ("it", "Accende la luce")])
+            .with_hazards(&[Hazard::FireHazard, Hazard::ElectricEnergyConsumption])
+            .with_parameters(&[
+                ParameterDescriptor::rangef64_with_default("brightness", (0., 20., 0.1), 5.)
+                    .localized_descriptions(&[("en", "Brightness")]),
+                ParameterDescriptor::bool("save-energy", false),
+            ])
+            .with_energy(2.5)
+            .with_cost(0.5)
+            .with_access_level(AccessLevel::Administrator)
+            .with_rate_limit(RateLimit::cooldown(1000)),
+        RouteDescriptor::get("/state"),
+    ];
 
     #[test]
     fn test_all_routes() {
         assert_eq!(
             serialize(
-                Route::get("/route")
+                Route::<2, 2>::get("/route")
                     .description("A GET route")
                     .serialize_data()
             ),
             json!({
                 "name": "/route",
+                "path": "/route",
                 "description": "A GET route",
                 "REST kind": "Get",
                 "response kind": "Ok"
@@ -265,12 +1020,13 @@ mod tests {
 
         assert_eq!(
             serialize(
-                Route::put("/route")
+                Route::<2, 2>::put("/route")
                     .description("A PUT route")
                     .serialize_data()
             ),
             json!({
                 "name": "/route",
+                "path": "/route",
                 "description": "A PUT route",
                 "REST kind": "Put",
                 "response kind": "Ok"
@@ -279,12 +1035,13 @@ mod tests {
 
         assert_eq!(
             serialize(
-                Route::post("/route")
+                Route::<2, 2>::post("/route")
                     .description("A POST route")
                     .serialize_data()
             ),
             json!({
                 "name": "/route",
+                "path": "/route",
                 "description": "A POST route",
                 "REST kind": "Post",
                 "response kind": "Ok"
@@ -293,12 +1050,13 @@ mod tests {
 
         assert_eq!(
             serialize(
-                Route::delete("/route")
+                Route::<2, 2>::delete("/route")
                     .description("A DELETE route")
                     .serialize_data()
             ),
             json!({
                 "name": "/route",
+                "path": "/route",
                 "description": "A DELETE route",
                 "REST kind": "Delete",
                 "response kind": "Ok"
@@ -322,6 +1080,7 @@ mod tests {
             ),
             json!({
                 "name": "/route",
+                "path": "/route",
                 "description": "A GET route",
                 "REST kind": "Get",
                 "response kind": "Ok",
@@ -332,12 +1091,54 @@ mod tests {
                 ],
             })
         );
+
+        // A route without hazards and parameters returns empty collections.
+        let route = Route::<2, 2>::get("/route");
+        assert!(route.hazards().is_empty());
+        assert!(route.parameters().kind("brightness").is_none());
+        assert!(route.serialize_data().hazards().is_empty());
+    }
+
+    #[test]
+    fn test_linear_backing() {
+        const ROUTES: [&str; 10] = ["/a", "/b", "/c", "/d", "/e", "/f", "/g", "/h", "/i", "/j"];
+
+        let mut linear = RouteConfigsBuilder::<3, 0, 9, Linear>::new();
+        let mut hashed = RouteConfigsBuilder::<4, 2, 16>::new();
+        for route in &ROUTES[..9] {
+            linear
+                .add(
+                    Route::put(route)
+                        .with_hazards(Hazards::init(Hazard::FireHazard))
+                        .with_parameters(Parameters::new()),
+                )
+                .unwrap();
+            hashed
+                .add(Route::put(route).with_hazards(Hazards::init(Hazard::FireHazard)))
+                .unwrap();
+        }
+        assert_eq!(
+            linear
+                .add(
+                    Route::put(ROUTES[9])
+                        .with_hazards(Hazards::new())
+                        .with_parameters(Parameters::new()),
+                )
+                .unwrap_err()
+                .kind(),
+            ErrorKind::Capacity
+        );
+
+        let linear = linear.build();
+        assert_eq!(linear.len(), 9);
+        assert_eq!(serialize(linear), serialize(hashed.build()));
     }
 
     #[test]
     fn test_all_parameters() {
         let expected = json!({
             "name": "/route",
+            "path": "/route",
             "description": "A GET route",
             "REST kind": "Get",
             "response kind": "Ok",
@@ -381,6 +1182,7 @@ mod tests {
     fn test_complete_route() {
         let expected = json!({
             "name": "/route",
+            "path": "/route",
             "description": "A GET route",
             "REST kind": "Get",
             "response kind": "Ok",
@@ -430,4 +1232,295 @@ mod tests {
             expected
         );
     }
+
+    #[test]
+    fn test_localized_descriptions() {
+        assert_eq!(
+            serialize(
+                Route::put("/route")
+                    .description("A PUT route")
+                    .localized_descriptions(&[("en", "A PUT route"), ("it", "Una rotta PUT")])
+                    .with_parameters(
+                        Parameters::<2>::new().u8("u8", 0).localized_descriptions(
+                            "u8",
+                            &[("en", "A value"), ("it", "Un valore")]
+                        )
+                    )
+                    .serialize_data()
+            ),
+            json!({
+                "name": "/route",
+                "path": "/route",
+                "description": "A PUT route",
+                "descriptions": {
+                    "en": "A PUT route",
+                    "it": "Una rotta PUT"
+                },
+                "REST kind": "Put",
+                "response kind": "Ok",
+                "parameters": {
+                    "u8": {
+                        "U8": {
+                            "default": 0,
+                            "min": 0,
+                            "max": 255
+                        }
+                    }
+                },
+                "parameters descriptions": {
+                    "u8": {
+                        "en": "A value",
+                        "it": "Un valore"
+                    }
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn test_route_configs_builder() {
+        let mut builder = RouteConfigsBuilder::<4, 2, 4>::new()
+            .route(Route::get("/light").with_hazards(Hazards::new()))
+            .unwrap()
+            .route(Route::put("/light").with_hazards(Hazards::init(Hazard::FireHazard)))
+            .unwrap();
+
+        assert_eq!(
+            builder
+                .add(Route::get("/light").with_hazards(Hazards::init(Hazard::FireHazard)))
+                .unwrap_err()
+                .kind(),
+            ErrorKind::DuplicateRoute
+        );
+        assert_eq!(
+            builder
+                .add(Route::get("light").with_hazards(Hazards::new()))
+                .unwrap_err()
+                .kind(),
+            ErrorKind::Route
+        );
+        assert_eq!(
+            builder
+                .add(Route::get("/light?on").with_hazards(Hazards::new()))
+                .unwrap_err()
+                .kind(),
+            ErrorKind::Route
+        );
+
+        let mut warnings = builder.warnings().iter();
+        assert_eq!(
+            warnings.next(),
+            Some(&RouteWarning {
+                route: "/light",
+                rest_kind: RestKind::Put,
+                conflicting_rest_kind: RestKind::Get,
+            })
+        );
+        assert_eq!(warnings.next(), None);
+
+        builder
+            .add(Route::post("/light-on_1.0").with_hazards(Hazards::new()))
+            .unwrap();
+        builder
+            .add(Route::delete("/light").with_hazards(Hazards::new()))
+            .unwrap();
+        assert_eq!(
+            builder
+                .add(Route::get("/other").with_hazards(Hazards::new()))
+                .unwrap_err()
+                .kind(),
+            ErrorKind::Capacity
+        );
+
+        assert_eq!(builder.build().len(), 4);
+    }
+
+    #[test]
+    fn test_route_warnings_capacity() {
+        let mut builder = RouteConfigsBuilder::<2, 2, 4>::new()
+            .route(Route::get("/light").with_hazards(Hazards::new()))
+            .unwrap()
+            .route(Route::put("/light").with_hazards(Hazards::init(Hazard::FireHazard)))
+            .unwrap()
+            .route(
+                Route::post("/light")
+                    .with_hazards(Hazards::init(Hazard::ElectricEnergyConsumption)),
+            )
+            .unwrap();
+        assert_eq!(builder.warnings().len(), 3);
+
+        // A route conflicting with all the previous ones exceeds the warnings.
+        assert_eq!(
+            builder
+                .add(Route::delete("/light").with_hazards(Hazards::init(Hazard::FireHazard)))
+                .unwrap_err()
+                .kind(),
+            ErrorKind::Capacity
+        );
+        assert_eq!(builder.warnings().len(), 3);
+        assert_eq!(builder.build().len(), 3);
+    }
+
+    #[test]
+    fn test_check_routes() {
+        assert!(check_routes(&[("/light", RestKind::Get), ("/light", RestKind::Put)]).is_ok());
+        assert_eq!(
+            check_routes(&[("/light", RestKind::Get), ("light", RestKind::Put)])
+                .unwrap_err()
+                .kind(),
+            ErrorKind::Route
+        );
+        assert_eq!(
+            check_routes(&[("", RestKind::Get)]).unwrap_err().kind(),
+            ErrorKind::Route
+        );
+        assert_eq!(
+            check_routes(&[("/light", RestKind::Get), ("/light", RestKind::Get)])
+                .unwrap_err()
+                .kind(),
+            ErrorKind::DuplicateRoute
+        );
+    }
+
+    #[test]
+    fn test_route_descriptor() {
+        let route_configs = RouteConfigsBuilder::<2, 2, 2>::new()
+            .route(
+                Route::put("/on")
+                    .description("Turns the light on")
+                    .localized_descriptions(&[
+                        ("en", "Turns the light on"),
+                        ("it", "Accende la luce"),
+                    ])
+                    .with_hazards(
+                        Hazards::new()
+                            .insert(Hazard::FireHazard)
+                            .insert(Hazard::ElectricEnergyConsumption),
+                    )
+                    .with_parameters(
+                        Parameters::new()
+                            .rangef64_with_default("brightness", (0., 20., 0.1), 5.)
+                            .localized_descriptions("brightness", &[("en", "Brightness")])
+                            .bool("save-energy", false),
+                    )
+                    .with_energy(2.5)
+                    .with_cost(0.5)
+                    .with_access_level(AccessLevel::Administrator)
+                    .with_rate_limit(RateLimit::cooldown(1000)),
+            )
+            .unwrap()
+            .route(
+                Route::get("/state")
+                    .with_hazards(Hazards::new())
+                    .with_parameters(Parameters::new()),
+            )
+            .unwrap()
+            .build();
+
+        assert_eq!(serialize(ROUTES), serialize(route_configs));
+
+        let route = &ROUTES[0];
+        assert_eq!(route.route(), "/on");
+        assert_eq!(route.kind(), RestKind::Put);
+        assert_eq!(route.hazards().len(), 2);
+        assert_eq!(
+            route
+                .parameter("brightness")
+                .unwrap()
+                .kind()
+                .default_value(),
+            Parameters::<2>::new()
+                .rangef64_with_default("brightness", (0., 20., 0.1), 5.)
+                .default_value("brightness")
+                .unwrap()
+        );
+        assert!(route.parameter("missing").is_none());
+        assert_eq!(route.energy(), Some(2.5));
+        assert_eq!(ROUTES[1].cost(), None);
+        assert!(route.authorize(AccessLevel::User).is_err());
+        assert!(ROUTES[1].authorize(AccessLevel::Guest).is_ok());
+        assert_eq!(route.rate_limit(), Some(RateLimit::cooldown(1000)));
+    }
+
+    #[test]
+    fn test_route_estimates() {
+        let route = Route::<2, 2>::put("/boost-heat")
+            .with_energy(1500.)
+            .with_cost(0.25);
+        assert_eq!(route.energy(), Some(1500.));
+        assert_eq!(route.cost(), Some(0.25));
+
+        assert_eq!(
+            serialize(route.serialize_data()),
+            json!({
+                "name": "/boost-heat",
+                "path": "/boost-heat",
+                "description": null,
+                "energy per invocation": 1500.0,
+                "cost per invocation": 0.25,
+                "REST kind": "Put",
+                "response kind": "Ok"
+            })
+        );
+    }
+
+    #[test]
+    fn test_route_access_level() {
+        let route =
+            Route::<2, 2>::put("/factory-reset").with_access_level(AccessLevel::Administrator);
+        assert_eq!(route.access_level(), AccessLevel::Administrator);
+        assert_eq!(
+            Route::<2, 2>::get("/state").access_level(),
+            AccessLevel::Guest
+        );
+
+        let route_config = route.serialize_data();
+        assert!(route_config.authorize(AccessLevel::Administrator).is_ok());
+        assert_eq!(
+            route_config
+                .authorize(AccessLevel::Guest)
+                .unwrap_err()
+                .kind(),
+            ErrorKind::Authorization
+        );
+
+        assert_eq!(
+            serialize(route_config),
+            json!({
+                "name": "/factory-reset",
+                "path": "/factory-reset",
+                "description": null,
+                "access level": "Administrator",
+                "REST kind": "Put",
+                "response kind": "Ok"
+            })
+        );
+    }
+
+    #[test]
+    fn test_route_rate_limit() {
+        let route = Route::<2, 2>::put("/relay")
+            .with_rate_limit(RateLimit::new(5, 60_000).with_min_interval(500));
+        assert_eq!(
+            route.rate_limit().map(|rate_limit| rate_limit.calls()),
+            Some(5)
+        );
+        assert!(Route::<2, 2>::get("/state").rate_limit().is_none());
+
+        assert_eq!(
+            serialize(route.serialize_data()),
+            json!({
+                "name": "/relay",
+                "path": "/relay",
+                "description": null,
+                "rate limit": {
+                    "calls": 5,
+                    "window": 60_000,
+                    "minimum interval": 500
+                },
+                "REST kind": "Put",
+                "response kind": "Ok"
+            })
+        );
+    }
 }
@@ -0,0 +1,417 @@
+use core::fmt::{Debug, Formatter, Result as FmtResult};
+use core::hash::Hash;
+use core::slice::Iter;
+
+use heapless::{FnvIndexMap, FnvIndexSet, IndexMapIter, IndexSetIter, Vec};
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// A constant empty storage.
+pub trait Empty {
+    /// The empty storage.
+    const EMPTY: Self;
+}
+
+impl<V, const N: usize> Empty for FnvIndexSet<V, N> {
+    const EMPTY: Self = FnvIndexSet::new();
+}
+
+impl<K, V, const N: usize> Empty for FnvIndexMap<K, V, N> {
+    const EMPTY: Self = FnvIndexMap::new();
+}
+
+impl<V, const N: usize> Empty for Vec<V, N> {
+    const EMPTY: Self = Vec::new();
+}
+
+/// The storage backing a collection.
+///
+/// A [`Hashed`] collection looks up its elements through a hash table and
+/// accepts only a power of two as capacity. A [`Linear`] collection looks up
+/// its elements with a linear scan and accepts any capacity, zero included,
+/// so it is suitable for a small number of elements.
+///
+/// Both backings keep the insertion order, so they produce the same
+/// serialization.
+pub trait Backing: sealed::Sealed + Copy + Debug + Eq + Hash {
+    /// Storage of a set.
+    type Set<V: Eq + Hash, const N: usize>: Empty;
+    /// Iterator over the elements of a set.
+    type SetIter<'a, V: 'a>: Iterator<Item = &'a V> + Clone;
+    /// Storage of a map.
+    type Map<K: Eq + Hash, V, const N: usize>: Empty;
+    /// Iterator over the entries of a map.
+    type MapIter<'a, K: 'a, V: 'a>: Iterator<Item = (&'a K, &'a V)> + Clone;
+
+    #[doc(hidden)]
+    fn set_iter<'a, V: Eq + Hash + 'a, const N: usize>(
+        set: &'a Self::Set<V, N>,
+    ) -> Self::SetIter<'a, V>;
+    #[doc(hidden)]
+    fn set_len<V: Eq + Hash, const N: usize>(set: &Self::Set<V, N>) -> usize;
+    #[doc(hidden)]
+    fn set_contains<V: Eq + Hash, const N: usize>(set: &Self::Set<V, N>, element: &V) -> bool;
+    #[doc(hidden)]
+    fn set_insert<V: Eq + Hash, const N: usize>(
+        set: &mut Self::Set<V, N>,
+        element: V,
+    ) -> core::result::Result<(), V>;
+    #[doc(hidden)]
+    fn set_retain<V: Eq + Hash, const N: usize, F: FnMut(&V) -> bool>(
+        set: &mut Self::Set<V, N>,
+        predicate: F,
+    );
+    #[doc(hidden)]
+    fn set_clear<V: Eq + Hash, const N: usize>(set: &mut Self::Set<V, N>);
+    #[doc(hidden)]
+    fn set_clone<V: Eq + Hash + Clone, const N: usize>(set: &Self::Set<V, N>) -> Self::Set<V, N>;
+    #[doc(hidden)]
+    fn set_fmt<V: Eq + Hash + Debug, const N: usize>(
+        set: &Self::Set<V, N>,
+        formatter: &mut Formatter,
+    ) -> FmtResult;
+
+    #[doc(hidden)]
+    fn map_iter<'a, K: Eq + Hash + 'a, V: 'a, const N: usize>(
+        map: &'a Self::Map<K, V, N>,
+    ) -> Self::MapIter<'a, K, V>;
+    #[doc(hidden)]
+    fn map_len<K: Eq + Hash, V, const N: usize>(map: &Self::Map<K, V, N>) -> usize;
+    #[doc(hidden)]
+    fn map_get<'a, K: Eq + Hash, V, const N: usize>(
+        map: &'a Self::Map<K, V, N>,
+        key: &K,
+    ) -> Option<&'a V>;
+    #[doc(hidden)]
+    fn map_get_mut<'a, K: Eq + Hash, V, const N: usize>(
+        map: &'a mut Self::Map<K, V, N>,
+        key: &K,
+    ) -> Option<&'a mut V>;
+    #[doc(hidden)]
+    fn map_insert<K: Eq + Hash, V, const N: usize>(
+        map: &mut Self::Map<K, V, N>,
+        key: K,
+        value: V,
+    ) -> core::result::Result<(), (K, V)>;
+    #[doc(hidden)]
+    fn map_remove<K: Eq + Hash, V, const N: usize>(
+        map: &mut Self::Map<K, V, N>,
+        key: &K,
+    ) -> Option<V>;
+    #[doc(hidden)]
+    fn map_retain<K: Eq + Hash, V, const N: usize, F: FnMut(&K, &mut V) -> bool>(
+        map: &mut Self::Map<K, V, N>,
+        predicate: F,
+    );
+    #[doc(hidden)]
+    fn map_clear<K: Eq + Hash, V, const N: usize>(map: &mut Self::Map<K, V, N>);
+    #[doc(hidden)]
+    fn map_clone<K: Eq + Hash + Clone, V: Clone, const N: usize>(
+        map: &Self::Map<K, V, N>,
+    ) -> Self::Map<K, V, N>;
+    #[doc(hidden)]
+    fn map_fmt<K: Eq + Hash + Debug, V: Debug, const N: usize>(
+        map: &Self::Map<K, V, N>,
+        formatter: &mut Formatter,
+    ) -> FmtResult;
+}
+
+/// Collections backed by a hash table.
+///
+/// **For alignment reasons, they accept only a power of two
+/// as number of elements.**
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Hashed;
+
+/// Collections backed by an array looked up with a linear scan.
+///
+/// They accept any number of elements, zero included.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Linear;
+
+impl sealed::Sealed for Hashed {}
+
+impl sealed::Sealed for Linear {}
+
+impl Backing for Hashed {
+    type Set<V: Eq + Hash, const N: usize> = FnvIndexSet<V, N>;
+    type SetIter<'a, V: 'a> = IndexSetIter<'a, V>;
+    type Map<K: Eq + Hash, V, const N: usize> = FnvIndexMap<K, V, N>;
+    type MapIter<'a, K: 'a, V: 'a> = IndexMapIter<'a, K, V>;
+
+    fn set_iter<'a, V: Eq + Hash + 'a, const N: usize>(
+        set: &'a Self::Set<V, N>,
+    ) -> Self::SetIter<'a, V> {
+        set.iter()
+    }
+
+    fn set_len<V: Eq + Hash, const N: usize>(set: &Self::Set<V, N>) -> usize {
+        set.len()
+    }
+
+    fn set_contains<V: Eq + Hash, const N: usize>(set: &Self::Set<V, N>, element: &V) -> bool {
+        set.contains(element)
+    }
+
+    fn set_insert<V: Eq + Hash, const N: usize>(
+        set: &mut Self::Set<V, N>,
+        element: V,
+    ) -> core::result::Result<(), V> {
+        set.insert(element).map(|_| ())
+    }
+
+    fn set_retain<V: Eq + Hash, const N: usize, F: FnMut(&V) -> bool>(
+        set: &mut Self::Set<V, N>,
+        predicate: F,
+    ) {
+        set.retain(predicate);
+    }
+
+    fn set_clear<V: Eq + Hash, const N: usize>(set: &mut Self::Set<V, N>) {
+        set.clear();
+    }
+
+    fn set_clone<V: Eq + Hash + Clone, const N: usize>(set: &Self::Set<V, N>) -> Self::Set<V, N> {
+        set.clone()
+    }
+
+    fn set_fmt<V: Eq + Hash + Debug, const N: usize>(
+        set: &Self::Set<V, N>,
+        formatter: &mut Formatter,
+    ) -> FmtResult {
+        set.fmt(formatter)
+    }
+
+    fn map_iter<'a, K: Eq + Hash + 'a, V: 'a, const N: usize>(
+        map: &'a Self::Map<K, V, N>,
+    ) -> Self::MapIter<'a, K, V> {
+        map.iter()
+    }
+
+    fn map_len<K: Eq + Hash, V, const N: usize>(map: &Self::Map<K, V, N>) -> usize {
+        map.len()
+    }
+
+    fn map_get<'a, K: Eq + Hash, V, const N: usize>(
+        map: &'a Self::Map<K, V, N>,
+        key: &K,
+    ) -> Option<&'a V> {
+        map.get(key)
+    }
+
+    fn map_get_mut<'a, K: Eq + Hash, V, const N: usize>(
+        map: &'a mut Self::Map<K, V, N>,
+        key: &K,
+    ) -> Option<&'a mut V> {
+        map.get_mut(key)
+    }
+
+    fn map_insert<K: Eq + Hash, V, const N: usize>(
+        map: &mut Self::Map<K, V, N>,
+        key: K,
+        value: V,
+    ) -> core::result::Result<(), (K, V)> {
+        map.insert(key, value).map(|_| ())
+    }
+
+    fn map_remove<K: Eq + Hash, V, const N: usize>(
+        map: &mut Self::Map<K, V, N>,
+        key: &K,
+    ) -> Option<V> {
+        if !map.contains_key(key) {
+            return None;
+        }
+        // The map is rebuilt to preserve the insertion order of the
+        // remaining entries. Since a map is consumed from its last entry,
+        // the entries are moved twice.
+        let mut removed = None;
+        let mut reversed = FnvIndexMap::<K, V, N>::new();
+        for (stored, value) in core::mem::replace(map, FnvIndexMap::new()) {
+            if stored == *key {
+                removed = Some(value);
+            } else {
+                let _ = reversed.insert(stored, value);
+            }
+        }
+        for (stored, value) in reversed {
+            let _ = map.insert(stored, value);
+        }
+        removed
+    }
+
+    fn map_retain<K: Eq + Hash, V, const N: usize, F: FnMut(&K, &mut V) -> bool>(
+        map: &mut Self::Map<K, V, N>,
+        predicate: F,
+    ) {
+        map.retain(predicate);
+    }
+
+    fn map_clear<K: Eq + Hash, V, const N: usize>(map: &mut Self::Map<K, V, N>) {
+        map.clear();
+    }
+
+    fn map_clone<K: Eq + Hash + Clone, V: Clone, const N: usize>(
+        map: &Self::Map<K, V, N>,
+    ) -> Self::Map<K, V, N> {
+        map.clone()
+    }
+
+    fn map_fmt<K: Eq + Hash + Debug, V: Debug, const N: usize>(
+        map: &Self::Map<K, V, N>,
+        formatter: &mut Formatter,
+    ) -> FmtResult {
+        map.fmt(formatter)
+    }
+}
+
+/// An iterator over the entries of a [`Linear`] map.
+///
+/// **It iterates in the insertion order.**
+#[derive(Debug)]
+pub struct LinearMapIter<'a, K, V>(Iter<'a, (K, V)>);
+
+impl<K, V> Clone for LinearMapIter<'_, K, V> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<'a, K, V> Iterator for LinearMapIter<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|(key, value)| (key, value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl Backing for Linear {
+    type Set<V: Eq + Hash, const N: usize> = Vec<V, N>;
+    type SetIter<'a, V: 'a> = Iter<'a, V>;
+    type Map<K: Eq + Hash, V, const N: usize> = Vec<(K, V), N>;
+    type MapIter<'a, K: 'a, V: 'a> = LinearMapIter<'a, K, V>;
+
+    fn set_iter<'a, V: Eq + Hash + 'a, const N: usize>(
+        set: &'a Self::Set<V, N>,
+    ) -> Self::SetIter<'a, V> {
+        set.iter()
+    }
+
+    fn set_len<V: Eq + Hash, const N: usize>(set: &Self::Set<V, N>) -> usize {
+        set.len()
+    }
+
+    fn set_contains<V: Eq + Hash, const N: usize>(set: &Self::Set<V, N>, element: &V) -> bool {
+        set.contains(element)
+    }
+
+    fn set_insert<V: Eq + Hash, const N: usize>(
+        set: &mut Self::Set<V, N>,
+        element: V,
+    ) -> core::result::Result<(), V> {
+        if set.contains(&element) {
+            return Ok(());
+        }
+        set.push(element)
+    }
+
+    fn set_retain<V: Eq + Hash, const N: usize, F: FnMut(&V) -> bool>(
+        set: &mut Self::Set<V, N>,
+        predicate: F,
+    ) {
+        set.retain(predicate);
+    }
+
+    fn set_clear<V: Eq + Hash, const N: usize>(set: &mut Self::Set<V, N>) {
+        set.clear();
+    }
+
+    fn set_clone<V: Eq + Hash + Clone, const N: usize>(set: &Self::Set<V, N>) -> Self::Set<V, N> {
+        set.clone()
+    }
+
+    fn set_fmt<V: Eq + Hash + Debug, const N: usize>(
+        set: &Self::Set<V, N>,
+        formatter: &mut Formatter,
+    ) -> FmtResult {
+        formatter.debug_set().entries(set).finish()
+    }
+
+    fn map_iter<'a, K: Eq + Hash + 'a, V: 'a, const N: usize>(
+        map: &'a Self::Map<K, V, N>,
+    ) -> Self::MapIter<'a, K, V> {
+        LinearMapIter(map.iter())
+    }
+
+    fn map_len<K: Eq + Hash, V, const N: usize>(map: &Self::Map<K, V, N>) -> usize {
+        map.len()
+    }
+
+    fn map_get<'a, K: Eq + Hash, V, const N: usize>(
+        map: &'a Self::Map<K, V, N>,
+        key: &K,
+    ) -> Option<&'a V> {
+        map.iter()
+            .find_map(|(stored, value)| (stored == key).then_some(value))
+    }
+
+    fn map_get_mut<'a, K: Eq + Hash, V, const N: usize>(
+        map: &'a mut Self::Map<K, V, N>,
+        key: &K,
+    ) -> Option<&'a mut V> {
+        map.iter_mut()
+            .find_map(|(stored, value)| (stored == key).then_some(value))
+    }
+
+    fn map_insert<K: Eq + Hash, V, const N: usize>(
+        map: &mut Self::Map<K, V, N>,
+        key: K,
+        value: V,
+    ) -> core::result::Result<(), (K, V)> {
+        if let Some(stored) = Self::map_get_mut(map, &key) {
+            *stored = value;
+            return Ok(());
+        }
+        map.push((key, value))
+    }
+
+    fn map_remove<K: Eq + Hash, V, const N: usize>(
+        map: &mut Self::Map<K, V, N>,
+        key: &K,
+    ) -> Option<V> {
+        let index = map.iter().position(|(stored, _)| stored == key)?;
+        Some(map.remove(index).1)
+    }
+
+    fn map_retain<K: Eq + Hash, V, const N: usize, F: FnMut(&K, &mut V) -> bool>(
+        map: &mut Self::Map<K, V, N>,
+        mut predicate: F,
+    ) {
+        map.retain_mut(|(key, value)| predicate(key, value));
+    }
+
+    fn map_clear<K: Eq + Hash, V, const N: usize>(map: &mut Self::Map<K, V, N>) {
+        map.clear();
+    }
+
+    fn map_clone<K: Eq + Hash + Clone, V: Clone, const N: usize>(
+        map: &Self::Map<K, V, N>,
+    ) -> Self::Map<K, V, N> {
+        map.clone()
+    }
+
+    fn map_fmt<K: Eq + Hash + Debug, V: Debug, const N: usize>(
+        map: &Self::Map<K, V, N>,
+        formatter: &mut Formatter,
+    ) -> FmtResult {
+        formatter
+            .debug_map()
+            .entries(map.iter().map(|(key, value)| (key, value)))
+            .finish()
+    }
+}
@@ -1,36 +1,105 @@
+use core::fmt::{Debug, Formatter, Result as FmtResult};
 use core::hash::Hash;
+use core::marker::PhantomData;
 
-use heapless::{FnvIndexMap, IndexMapIter};
+use serde::de::{Error as DeError, MapAccess, Visitor};
+use serde::ser::SerializeMap;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
-use serde::{Deserialize, Serialize};
+use crate::error::{Error, ErrorKind, Result};
+
+use super::backing::{Backing, Empty, Hashed};
 
 /// A map of elements for internal storage.
-#[derive(Debug, Clone)]
-pub struct Map<K: Eq + Hash, V, const N: usize>(FnvIndexMap<K, V, N>);
+///
+/// Its [`Backing`] is [`Hashed`] by default.
+pub struct Map<K: Eq + Hash, V, const N: usize, B: Backing = Hashed>(B::Map<K, V, N>);
 
 /// A serializable map of elements.
-#[derive(Debug, Clone, Serialize)]
-pub struct SerialMap<K: Eq + Hash, V, const N: usize>(FnvIndexMap<K, V, N>);
+///
+/// Its [`Backing`] is [`Hashed`] by default.
+pub struct SerialMap<K: Eq + Hash, V, const N: usize, B: Backing = Hashed>(B::Map<K, V, N>);
 
 /// A serializable and deserializable map of elements.
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct OutputMap<K: Eq + Hash, V, const N: usize>(FnvIndexMap<K, V, N>);
+///
+/// Its [`Backing`] is [`Hashed`] by default.
+pub struct OutputMap<K: Eq + Hash, V, const N: usize, B: Backing = Hashed>(B::Map<K, V, N>);
+
+impl<'de, K, V, const N: usize, B> Deserialize<'de> for OutputMap<K, V, N, B>
+where
+    K: Eq + Hash + Deserialize<'de>,
+    V: Deserialize<'de>,
+    B: Backing,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> core::result::Result<Self, D::Error> {
+        struct MapVisitor<K, V, const N: usize, B>(PhantomData<(K, V, B)>);
+
+        impl<'de, K, V, const N: usize, B> Visitor<'de> for MapVisitor<K, V, N, B>
+        where
+            K: Eq + Hash + Deserialize<'de>,
+            V: Deserialize<'de>,
+            B: Backing,
+        {
+            type Value = OutputMap<K, V, N, B>;
+
+            fn expecting(&self, formatter: &mut Formatter) -> FmtResult {
+                formatter.write_str("a map")
+            }
+
+            fn visit_map<A: MapAccess<'de>>(
+                self,
+                mut map: A,
+            ) -> core::result::Result<Self::Value, A::Error> {
+                let mut entries = OutputMap::new();
+                while let Some((key, value)) = map.next_entry()? {
+                    if entries.try_add(key, value).is_err() {
+                        return Err(A::Error::invalid_length(N + 1, &self));
+                    }
+                }
+                Ok(entries)
+            }
+        }
+
+        deserializer.deserialize_map(MapVisitor(PhantomData))
+    }
+}
+
+macro_rules! serialize_map {
+    ($impl:ident) => {
+        impl<K, V, const N: usize, B> Serialize for $impl<K, V, N, B>
+        where
+            K: Eq + Hash + Serialize,
+            V: Serialize,
+            B: Backing,
+        {
+            fn serialize<S: Serializer>(
+                &self,
+                serializer: S,
+            ) -> core::result::Result<S::Ok, S::Error> {
+                let mut map = serializer.serialize_map(Some(self.len()))?;
+                for (key, value) in self {
+                    map.serialize_entry(key, value)?;
+                }
+                map.end()
+            }
+        }
+    };
+}
 
 macro_rules! from_map {
     ($for:ident) => {
-        impl<K, V, K1, V1, const N: usize> From<Map<K1, V1, N>> for $for<K, V, N>
+        impl<K, V, K1, V1, const N: usize, B> From<Map<K1, V1, N, B>> for $for<K, V, N, B>
         where
-            K: Clone + Copy + Eq + Hash + From<K1>,
-            V: Clone + Copy + Eq + From<V1>,
-            K1: Clone + Copy + Eq + Hash,
-            V1: Clone + Copy + Eq,
+            K: Eq + Hash + From<K1>,
+            V: From<V1>,
+            K1: Clone + Eq + Hash,
+            V1: Clone,
+            B: Backing,
         {
-            fn from(map: Map<K1, V1, N>) -> Self {
+            fn from(map: Map<K1, V1, N, B>) -> Self {
                 let mut new_map = Self::new();
                 for (key, value) in map.iter() {
-                    let _ = new_map
-                        .0
-                        .insert(K::from(key.clone()), V::from(value.clone()));
+                    new_map.add(K::from(key.clone()), V::from(value.clone()));
                 }
                 new_map
             }
@@ -40,39 +109,97 @@ macro_rules! from_map {
 
 macro_rules! map_implementation {
     ($impl:ident) => {
-        impl<'a, K, V, const N: usize> IntoIterator for &'a $impl<K, V, N>
+        impl<K, V, const N: usize, B> Clone for $impl<K, V, N, B>
         where
-            K: Clone + Copy + Eq + Hash,
-            V: Clone + Copy
+            K: Clone + Eq + Hash,
+            V: Clone,
+            B: Backing,
+        {
+            fn clone(&self) -> Self {
+                Self(B::map_clone(&self.0))
+            }
+        }
+
+        impl<K, V, const N: usize, B> Debug for $impl<K, V, N, B>
+        where
+            K: Debug + Eq + Hash,
+            V: Debug,
+            B: Backing,
+        {
+            fn fmt(&self, formatter: &mut Formatter) -> FmtResult {
+                struct Entries<'a, K: Eq + Hash, V, const N: usize, B: Backing>(&'a B::Map<K, V, N>);
+
+                impl<K: Debug + Eq + Hash, V: Debug, const N: usize, B: Backing> Debug
+                    for Entries<'_, K, V, N, B>
+                {
+                    fn fmt(&self, formatter: &mut Formatter) -> FmtResult {
+                        B::map_fmt(self.0, formatter)
+                    }
+                }
+
+                formatter
+                    .debug_tuple(stringify!($impl))
+                    .field(&Entries::<K, V, N, B>(&self.0))
+                    .finish()
+            }
+        }
+
+        impl<'a, K, V, const N: usize, B> IntoIterator for &'a $impl<K, V, N, B>
+        where
+            K: Eq + Hash,
+            B: Backing,
         {
             type Item = (&'a K, &'a V);
-            type IntoIter = IndexMapIter<'a, K, V>;
+            type IntoIter = B::MapIter<'a, K, V>;
 
             fn into_iter(self) -> Self::IntoIter {
                 self.iter()
             }
         }
 
-        impl<K, V, const N: usize> Default for $impl<K, V, N>
+        impl<K, V, const N: usize, B> Default for $impl<K, V, N, B>
         where
-            K: Clone + Copy + Eq + Hash,
-            V: Clone + Copy
+            K: Eq + Hash,
+            B: Backing,
         {
             fn default() -> Self {
                 Self::new()
             }
         }
 
-        impl<K, V, const N: usize> $impl<K, V, N>
+        impl<K, V, const N: usize, B> Extend<(K, V)> for $impl<K, V, N, B>
         where
-            K: Clone + Copy + Eq + Hash,
-            V: Clone + Copy
+            K: Eq + Hash,
+            B: Backing,
+        {
+            fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+                for (key, value) in iter {
+                    self.add(key, value);
+                }
+            }
+        }
+
+        impl<K, V, const N: usize, B> FromIterator<(K, V)> for $impl<K, V, N, B>
+        where
+            K: Eq + Hash,
+            B: Backing,
+        {
+            fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+                let mut elements = Self::new();
+                elements.extend(iter);
+                elements
+            }
+        }
+
+        impl<K, V, const N: usize, B> $impl<K, V, N, B>
+        where
+            K: Eq + Hash,
+            B: Backing,
         {
             #[doc = concat!("Creates a [`", stringify!($impl), "`].")]
             #[must_use]
-            #[inline]
-            pub fn new() -> Self {
-                Self(FnvIndexMap::new())
+            pub const fn new() -> Self {
+                Self(<B::Map<K, V, N> as Empty>::EMPTY)
             }
 
             #[doc = concat!("Initializes a [`", stringify!($impl), "`] with a determined element.")]
@@ -86,34 +213,34 @@ macro_rules! map_implementation {
             #[must_use]
             #[inline]
             pub fn insert(mut self, key: K, value: V) -> Self {
-                let _ = self.0.insert(key, value);
+                self.add(key, value);
                 self
             }
 
             #[doc = concat!("Adds an element to a [`", stringify!($impl), "`].")]
             #[inline]
             pub fn add(&mut self, key: K, value: V) {
-                let _ = self.0.insert(key, value);
+                let _ = B::map_insert(&mut self.0, key, value);
             }
 
             #[doc = concat!("Checks whether the [`", stringify!($impl), "`] is empty.")]
             #[must_use]
             #[inline]
             pub fn is_empty(&self) -> bool {
-                self.0.is_empty()
+                self.len() == 0
             }
 
             #[doc = concat!("Returns the [`", stringify!($impl), "`] length.")]
             #[must_use]
             #[inline]
             pub fn len(&self) -> usize {
-                self.0.len()
+                B::map_len(&self.0)
             }
 
             #[doc = concat!("Checks whether the [`", stringify!($impl), "`] contains the given key.")]
             #[inline]
             pub fn contains_key(&self, key: &K) -> bool {
-                self.0.contains_key(key)
+                self.get(key).is_some()
             }
 
             #[doc = concat!("Returns an iterator over the [`", stringify!($impl), "`].")]
@@ -121,16 +248,101 @@ macro_rules! map_implementation {
             #[doc = "**It iterates in the insertion order.**"]
             #[must_use]
             #[inline]
-            pub fn iter(&self) -> IndexMapIter<'_, K, V> {
-                self.0.iter()
+            pub fn iter(&self) -> B::MapIter<'_, K, V> {
+                B::map_iter(&self.0)
+            }
+
+            #[doc = concat!("Returns a reference to the value associated with a key of the [`", stringify!($impl), "`].")]
+            #[inline]
+            pub fn get(&self, key: &K) -> Option<&V> {
+                B::map_get(&self.0, key)
+            }
+
+            #[doc = concat!("Returns a mutable reference to the value associated with a key of the [`", stringify!($impl), "`].")]
+            #[inline]
+            pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+                B::map_get_mut(&mut self.0, key)
+            }
+
+            #[doc = concat!("Removes a key from the [`", stringify!($impl), "`], returning its value.")]
+            #[doc = ""]
+            #[doc = "**It preserves the insertion order of the remaining elements.**"]
+            #[inline]
+            pub fn remove(&mut self, key: &K) -> Option<V> {
+                B::map_remove(&mut self.0, key)
+            }
+
+            #[doc = concat!("Retains only the elements of the [`", stringify!($impl), "`] satisfying the predicate.")]
+            #[doc = ""]
+            #[doc = "**It preserves the insertion order of the remaining elements.**"]
+            #[inline]
+            pub fn retain<F: FnMut(&K, &mut V) -> bool>(&mut self, predicate: F) {
+                B::map_retain(&mut self.0, predicate);
+            }
+
+            #[doc = concat!("Removes all elements from the [`", stringify!($impl), "`].")]
+            #[inline]
+            pub fn clear(&mut self) {
+                B::map_clear(&mut self.0);
+            }
+
+            #[doc = concat!("Adds an element to a [`", stringify!($impl), "`], reporting an overflow.")]
+            #[doc = ""]
+            #[doc = "The value of an already present key is replaced."]
+            #[doc = ""]
+            #[doc = "# Errors"]
+            #[doc = ""]
+            #[doc = "If the key is not present and the capacity has been reached, an error is returned."]
+            #[inline]
+            pub fn try_add(&mut self, key: K, value: V) -> Result<()> {
+                B::map_insert(&mut self.0, key, value).map_err(|_| {
+                    Error::new(
+                        ErrorKind::Capacity,
+                        concat!("Impossible to add an element to a full `", stringify!($impl), "`."),
+                    )
+                })
+            }
+
+            #[doc = concat!("Adds all elements of an iterator to a [`", stringify!($impl), "`], reporting an overflow.")]
+            #[doc = ""]
+            #[doc = "Elements preceding the overflow are kept."]
+            #[doc = ""]
+            #[doc = "# Errors"]
+            #[doc = ""]
+            #[doc = "If the capacity is not enough to contain all elements, an error is returned."]
+            #[inline]
+            pub fn try_extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) -> Result<()> {
+                for (key, value) in iter {
+                    self.try_add(key, value)?;
+                }
+                Ok(())
             }
 
+            #[doc = concat!("Creates a [`", stringify!($impl), "`] from an iterator, reporting an overflow.")]
+            #[doc = ""]
+            #[doc = "# Errors"]
+            #[doc = ""]
+            #[doc = "If the capacity is not enough to contain all elements, an error is returned."]
+            #[inline]
+            pub fn try_from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Result<Self> {
+                let mut elements = Self::new();
+                elements.try_extend(iter)?;
+                Ok(elements)
+            }
+        }
+
+        impl<K, V, const N: usize, B> $impl<K, V, N, B>
+        where
+            K: Clone + Eq + Hash,
+            V: Clone,
+            B: Backing,
+        {
             #[doc = concat!("Initializes [`", stringify!($impl), "`] with a list of `(key, value)`.")]
             #[inline]
             pub fn init_with_elements(input_elements: &[(K, V)]) -> Self {
                 let mut elements = Self::new();
                 for (key, value) in input_elements.iter() {
-                    elements.add(*key, *value);
+                    elements.add(key.clone(), value.clone());
                 }
                 elements
             }
@@ -147,6 +359,11 @@ map_implementation!(SerialMap);
 // Output map implementation.
 map_implementation!(OutputMap);
 
+// Serial map serialization.
+serialize_map!(SerialMap);
+// Output map serialization.
+serialize_map!(OutputMap);
+
 // Convert from map into serial map.
 from_map!(SerialMap);
 // Convert from map into output map.
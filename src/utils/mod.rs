@@ -1,3 +1,5 @@
+// Storages backing the collections.
+mod backing;
 // All maps collections needed for internal storage and I/O tasks.
 mod maps;
 // All sets collections needed for internal storage and I/O tasks.
@@ -7,7 +9,34 @@ mod sets;
 pub mod string;
 
 /// All supported collections.
+///
+/// Each collection is generic over its [`Backing`](collections::Backing),
+/// which is [`Hashed`](collections::Hashed) by default. Hashed collections
+/// accept only a power of two as capacity. [`Linear`](collections::Linear)
+/// collections, such as [`LinearSet`](collections::LinearSet), accept any
+/// capacity, but look up their elements with a linear scan, so they are
+/// suitable for a small number of elements. Both produce the same
+/// serialization.
 pub mod collections {
+    pub use super::backing::{Backing, Hashed, Linear, LinearMapIter};
     pub use super::maps::{Map, OutputMap, SerialMap};
     pub use super::sets::{OutputSet, SerialSet, Set};
+
+    /// A [`Set`] backed by a [`Linear`] storage.
+    pub type LinearSet<V, const N: usize> = Set<V, N, Linear>;
+
+    /// A [`SerialSet`] backed by a [`Linear`] storage.
+    pub type SerialLinearSet<V, const N: usize> = SerialSet<V, N, Linear>;
+
+    /// An [`OutputSet`] backed by a [`Linear`] storage.
+    pub type OutputLinearSet<V, const N: usize> = OutputSet<V, N, Linear>;
+
+    /// A [`Map`] backed by a [`Linear`] storage.
+    pub type LinearMap<K, V, const N: usize> = Map<K, V, N, Linear>;
+
+    /// A [`SerialMap`] backed by a [`Linear`] storage.
+    pub type SerialLinearMap<K, V, const N: usize> = SerialMap<K, V, N, Linear>;
+
+    /// An [`OutputMap`] backed by a [`Linear`] storage.
+    pub type OutputLinearMap<K, V, const N: usize> = OutputMap<K, V, N, Linear>;
 }
@@ -1,32 +1,108 @@
+use core::fmt::{Debug, Formatter, Result as FmtResult};
 use core::hash::Hash;
+use core::marker::PhantomData;
 
-use heapless::{FnvIndexSet, IndexSetIter};
+use serde::de::{Error as DeError, SeqAccess, Visitor};
+use serde::ser::SerializeSeq;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
-use serde::{Deserialize, Serialize};
+use crate::error::{Error, ErrorKind, Result};
+
+use super::backing::{Backing, Empty, Hashed};
 
 /// A set of elements for internal storage.
-#[derive(Debug, Clone)]
-pub struct Set<V: Eq + Hash, const N: usize>(FnvIndexSet<V, N>);
+///
+/// Its [`Backing`] is [`Hashed`] by default.
+pub struct Set<V: Eq + Hash, const N: usize, B: Backing = Hashed>(B::Set<V, N>);
 
 /// A serializable set of elements.
-#[derive(Debug, Clone, Serialize)]
-pub struct SerialSet<V: Eq + Hash, const N: usize>(FnvIndexSet<V, N>);
+///
+/// Its [`Backing`] is [`Hashed`] by default.
+pub struct SerialSet<V: Eq + Hash, const N: usize, B: Backing = Hashed>(B::Set<V, N>);
 
 /// A serializable and deserializable set of elements.
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
-pub struct OutputSet<V: Eq + Hash, const N: usize>(FnvIndexSet<V, N>);
+///
+/// Its [`Backing`] is [`Hashed`] by default.
+pub struct OutputSet<V: Eq + Hash, const N: usize, B: Backing = Hashed>(B::Set<V, N>);
+
+// Two sets with the same elements are equal, regardless of their insertion
+// order.
+impl<V: Eq + Hash, const N: usize, B: Backing> PartialEq for OutputSet<V, N, B> {
+    fn eq(&self, other: &Self) -> bool {
+        self.len() == other.len() && self.iter().all(|element| other.contains(element))
+    }
+}
+
+impl<'de, V, const N: usize, B> Deserialize<'de> for OutputSet<V, N, B>
+where
+    V: Eq + Hash + Deserialize<'de>,
+    B: Backing,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> core::result::Result<Self, D::Error> {
+        struct SetVisitor<V, const N: usize, B>(PhantomData<(V, B)>);
+
+        impl<'de, V, const N: usize, B> Visitor<'de> for SetVisitor<V, N, B>
+        where
+            V: Eq + Hash + Deserialize<'de>,
+            B: Backing,
+        {
+            type Value = OutputSet<V, N, B>;
+
+            fn expecting(&self, formatter: &mut Formatter) -> FmtResult {
+                formatter.write_str("a sequence")
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(
+                self,
+                mut seq: A,
+            ) -> core::result::Result<Self::Value, A::Error> {
+                let mut elements = OutputSet::new();
+                while let Some(element) = seq.next_element()? {
+                    if elements.try_add(element).is_err() {
+                        return Err(A::Error::invalid_length(N + 1, &self));
+                    }
+                }
+                Ok(elements)
+            }
+        }
+
+        deserializer.deserialize_seq(SetVisitor(PhantomData))
+    }
+}
+
+macro_rules! serialize_set {
+    ($impl:ident) => {
+        impl<V, const N: usize, B> Serialize for $impl<V, N, B>
+        where
+            V: Eq + Hash + Serialize,
+            B: Backing,
+        {
+            fn serialize<S: Serializer>(
+                &self,
+                serializer: S,
+            ) -> core::result::Result<S::Ok, S::Error> {
+                let mut seq = serializer.serialize_seq(Some(self.len()))?;
+                for element in self {
+                    seq.serialize_element(element)?;
+                }
+                seq.end()
+            }
+        }
+    };
+}
 
 macro_rules! from_set {
     ($for:ident) => {
-        impl<V, V1, const N: usize> From<Set<V1, N>> for $for<V, N>
+        impl<V, V1, const N: usize, B> From<Set<V1, N, B>> for $for<V, N, B>
         where
-            V: Clone + Copy + Eq + Hash + From<V1>,
-            V1: Clone + Copy + Eq + Hash,
+            V: Eq + Hash + From<V1>,
+            V1: Clone + Eq + Hash,
+            B: Backing,
         {
-            fn from(set: Set<V1, N>) -> Self {
+            fn from(set: Set<V1, N, B>) -> Self {
                 let mut new_set = Self::new();
                 for element in set.iter() {
-                    let _ = new_set.0.insert(V::from(*element));
+                    new_set.add(V::from(element.clone()));
                 }
                 new_set
             }
@@ -36,35 +112,93 @@ macro_rules! from_set {
 
 macro_rules! set_implementation {
     ($impl:ident $(,$trait:ident)?) => {
-        impl<'a, V, const N: usize> IntoIterator for &'a $impl<V, N>
+        impl<V, const N: usize, B> Clone for $impl<V, N, B>
         where
-            V: Clone + Copy + Eq + Hash,
+            V: Clone + Eq + Hash,
+            B: Backing,
+        {
+            fn clone(&self) -> Self {
+                Self(B::set_clone(&self.0))
+            }
+        }
+
+        impl<V, const N: usize, B> Debug for $impl<V, N, B>
+        where
+            V: Debug + Eq + Hash,
+            B: Backing,
+        {
+            fn fmt(&self, formatter: &mut Formatter) -> FmtResult {
+                struct Elements<'a, V: Eq + Hash, const N: usize, B: Backing>(&'a B::Set<V, N>);
+
+                impl<V: Debug + Eq + Hash, const N: usize, B: Backing> Debug for Elements<'_, V, N, B> {
+                    fn fmt(&self, formatter: &mut Formatter) -> FmtResult {
+                        B::set_fmt(self.0, formatter)
+                    }
+                }
+
+                formatter
+                    .debug_tuple(stringify!($impl))
+                    .field(&Elements::<V, N, B>(&self.0))
+                    .finish()
+            }
+        }
+
+        impl<'a, V, const N: usize, B> IntoIterator for &'a $impl<V, N, B>
+        where
+            V: Eq + Hash,
+            B: Backing,
         {
             type Item = &'a V;
-            type IntoIter = IndexSetIter<'a, V>;
+            type IntoIter = B::SetIter<'a, V>;
 
             fn into_iter(self) -> Self::IntoIter {
                 self.iter()
             }
         }
 
-        impl<V, const N: usize> Default for $impl<V, N>
+        impl<V, const N: usize, B> Default for $impl<V, N, B>
         where
-            V: Clone + Copy + Eq + Hash,
+            V: Eq + Hash,
+            B: Backing,
         {
             fn default() -> Self {
                 Self::new()
             }
         }
 
-        impl<V, const N: usize> $impl<V, N>
+        impl<V, const N: usize, B> Extend<V> for $impl<V, N, B>
+        where
+            V: Eq + Hash,
+            B: Backing,
+        {
+            fn extend<I: IntoIterator<Item = V>>(&mut self, iter: I) {
+                for element in iter {
+                    self.add(element);
+                }
+            }
+        }
+
+        impl<V, const N: usize, B> FromIterator<V> for $impl<V, N, B>
+        where
+            V: Eq + Hash,
+            B: Backing,
+        {
+            fn from_iter<I: IntoIterator<Item = V>>(iter: I) -> Self {
+                let mut elements = Self::new();
+                elements.extend(iter);
+                elements
+            }
+        }
+
+        impl<V, const N: usize, B> $impl<V, N, B>
         where
-            V: Clone + Copy + Eq + Hash,
+            V: Eq + Hash,
+            B: Backing,
         {
             #[doc = concat!("Creates a [`", stringify!($impl), "`].")]
             #[must_use]
             pub const fn new() -> Self {
-                Self(FnvIndexSet::new())
+                Self(<B::Set<V, N> as Empty>::EMPTY)
             }
 
             #[doc = concat!("Initializes a [`", stringify!($impl), "`] with a determined element.")]
@@ -80,56 +214,143 @@ macro_rules! set_implementation {
             #[must_use]
             #[inline]
             pub fn insert(mut self, element: V) -> Self {
-                let _ = self.0.insert(element);
+                self.add(element);
                 self
             }
 
             #[doc = concat!("Adds an element to a [`", stringify!($impl), "`].")]
             #[inline]
             pub fn add(&mut self, element: V) {
-                let _ = self.0.insert(element);
+                let _ = B::set_insert(&mut self.0, element);
             }
 
             #[doc = concat!("Checks whether the [`", stringify!($impl), "`] is empty.")]
             #[inline]
             pub fn is_empty(&self) -> bool {
-                self.0.is_empty()
+                self.len() == 0
             }
 
             #[doc = concat!("Returns the [`", stringify!($impl), "`] length.")]
             #[inline]
             pub fn len(&self) -> usize {
-                self.0.len()
+                B::set_len(&self.0)
             }
 
             #[doc = concat!("Checks whether the [`", stringify!($impl), "`] contains the given element.")]
             #[inline]
             pub fn contains(&self, element: &V) -> bool {
-                self.0.contains(element)
+                B::set_contains(&self.0, element)
             }
 
             #[doc = concat!("Returns an iterator over the [`", stringify!($impl), "`].")]
             #[doc = ""]
             #[doc = "**It iterates in the insertion order.**"]
             #[inline]
-            pub fn iter(&self) -> IndexSetIter<'_, V> {
-                self.0.iter()
+            pub fn iter(&self) -> B::SetIter<'_, V> {
+                B::set_iter(&self.0)
+            }
+
+            #[doc = concat!("Returns the element of the [`", stringify!($impl), "`] equal to the given one.")]
+            #[inline]
+            pub fn get(&self, element: &V) -> Option<&V> {
+                self.iter().find(|stored| *stored == element)
             }
 
+            #[doc = concat!("Removes an element from the [`", stringify!($impl), "`].")]
+            #[doc = ""]
+            #[doc = "It returns whether the element was present."]
+            #[doc = ""]
+            #[doc = "**It preserves the insertion order of the remaining elements.**"]
+            #[inline]
+            pub fn remove(&mut self, element: &V) -> bool {
+                if !self.contains(element) {
+                    return false;
+                }
+                self.retain(|stored| stored != element);
+                true
+            }
+
+            #[doc = concat!("Retains only the elements of the [`", stringify!($impl), "`] satisfying the predicate.")]
+            #[doc = ""]
+            #[doc = "**It preserves the insertion order of the remaining elements.**"]
+            #[inline]
+            pub fn retain<F: FnMut(&V) -> bool>(&mut self, predicate: F) {
+                B::set_retain(&mut self.0, predicate);
+            }
+
+            #[doc = concat!("Removes all elements from the [`", stringify!($impl), "`].")]
+            #[inline]
+            pub fn clear(&mut self) {
+                B::set_clear(&mut self.0);
+            }
+
+            #[doc = concat!("Adds an element to a [`", stringify!($impl), "`], reporting an overflow.")]
+            #[doc = ""]
+            #[doc = "# Errors"]
+            #[doc = ""]
+            #[doc = "If the element is not present and the capacity has been reached, an error is returned."]
+            #[inline]
+            pub fn try_add(&mut self, element: V) -> Result<()> {
+                B::set_insert(&mut self.0, element).map_err(|_| {
+                    Error::new(
+                        ErrorKind::Capacity,
+                        concat!("Impossible to add an element to a full `", stringify!($impl), "`."),
+                    )
+                })
+            }
+
+            #[doc = concat!("Adds all elements of an iterator to a [`", stringify!($impl), "`], reporting an overflow.")]
+            #[doc = ""]
+            #[doc = "Elements preceding the overflow are kept."]
+            #[doc = ""]
+            #[doc = "# Errors"]
+            #[doc = ""]
+            #[doc = "If the capacity is not enough to contain all elements, an error is returned."]
+            #[inline]
+            pub fn try_extend<I: IntoIterator<Item = V>>(&mut self, iter: I) -> Result<()> {
+                for element in iter {
+                    self.try_add(element)?;
+                }
+                Ok(())
+            }
+
+            #[doc = concat!("Creates a [`", stringify!($impl), "`] from an iterator, reporting an overflow.")]
+            #[doc = ""]
+            #[doc = "# Errors"]
+            #[doc = ""]
+            #[doc = "If the capacity is not enough to contain all elements, an error is returned."]
+            #[inline]
+            pub fn try_from_iter<I: IntoIterator<Item = V>>(iter: I) -> Result<Self> {
+                let mut elements = Self::new();
+                elements.try_extend(iter)?;
+                Ok(elements)
+            }
+
+        }
+
+        impl<V, const N: usize, B> $impl<V, N, B>
+        where
+            V: Clone + Eq + Hash,
+            B: Backing,
+        {
             #[doc = concat!("Initializes [`", stringify!($impl), "`] with a list of elements.")]
             #[inline]
             pub fn init_with_elements(input_elements: &[V]) -> Self {
                 let mut elements = Self::new();
                 for element in input_elements.iter() {
-                    elements.add(*element);
+                    elements.add(element.clone());
                 }
                 elements
             }
 
             #[doc = concat!("Merges all elements from another [`", stringify!($impl), "`] into this one.")]
+            #[doc = ""]
+            #[doc = "Elements exceeding the capacity are discarded."]
             #[inline]
             pub fn merge(&mut self, element: &Self) {
-                self.0 = self.0.union(&element.0).copied().collect();
+                for element in element.iter() {
+                    self.add(element.clone());
+                }
             }
         }
     };
@@ -144,6 +365,11 @@ set_implementation!(SerialSet);
 // Output set implementation.
 set_implementation!(OutputSet);
 
+// Serial set serialization.
+serialize_set!(SerialSet);
+// Output set serialization.
+serialize_set!(OutputSet);
+
 // Convert from a set into a serial collection.
 from_set!(SerialSet);
 // Convert from a set into an output set.
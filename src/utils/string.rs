@@ -1,3 +1,5 @@
+use core::fmt::{Arguments, Display, Formatter};
+use core::ops::Deref;
 use core::str::FromStr;
 
 use heapless::String as OtherString;
@@ -16,6 +18,80 @@ impl<const N: usize> core::fmt::Write for String<N> {
     }
 }
 
+impl<const N: usize> Display for String<N> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl<const N: usize> Deref for String<N> {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<const N: usize> AsRef<str> for String<N> {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl<const N: usize> PartialEq<str> for String<N> {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl<const N: usize> PartialEq<&str> for String<N> {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl<const N: usize> TryFrom<&str> for String<N> {
+    type Error = Error;
+
+    fn try_from(text: &str) -> Result<Self> {
+        Self::new(text)
+    }
+}
+
+impl<const N: usize> FromStr for String<N> {
+    type Err = Error;
+
+    fn from_str(text: &str) -> Result<Self> {
+        Self::new(text)
+    }
+}
+
+/// Text appended by [`String::truncated_with_ellipsis`] to a truncated text.
+pub const ELLIPSIS: &str = "...";
+
+/// Creates a [`String`] using interpolation of runtime expressions,
+/// like [`format!`](https://doc.rust-lang.org/std/macro.format.html).
+///
+/// The capacity of the [`String`] is inferred from the context.
+///
+/// # Errors
+///
+/// If the formatted text is greater than the capacity, an error is returned.
+///
+/// ```
+/// use tosca_stack::format_string;
+/// use tosca_stack::string::String;
+///
+/// let text: String<16> = format_string!("{} lights", 3).unwrap();
+/// assert_eq!(text, "3 lights");
+/// ```
+#[macro_export]
+macro_rules! format_string {
+    ($($arg:tt)*) => {
+        $crate::string::String::from_fmt(::core::format_args!($($arg)*))
+    };
+}
+
 impl<const N: usize> String<N> {
     /// Creates an empty [`String`] with a fixed capacity of `N` bytes.
     #[must_use]
@@ -46,6 +122,87 @@ Characters might not be UTF-8 or its length is wrong.",
         Self::new(text).unwrap_or(Self::empty())
     }
 
+    /// Creates a [`String`] keeping the longest prefix of the input text
+    /// which fits in `N` bytes.
+    ///
+    /// The text is truncated on a character boundary, so the result is
+    /// always a valid UTF-8 text.
+    #[must_use]
+    pub fn truncated(text: &str) -> Self {
+        let mut string = Self::empty();
+        let _ = string.0.push_str(&text[..floor_char_boundary(text, N)]);
+        string
+    }
+
+    /// Creates a [`String`] keeping the longest prefix of the input text
+    /// which fits in `N` bytes together with an [`ELLIPSIS`].
+    ///
+    /// The [`ELLIPSIS`] is appended only when the text has been truncated.
+    /// If `N` is not enough to contain the [`ELLIPSIS`], the text is
+    /// truncated as in [`String::truncated`].
+    #[must_use]
+    pub fn truncated_with_ellipsis(text: &str) -> Self {
+        if text.len() <= N || N < ELLIPSIS.len() {
+            return Self::truncated(text);
+        }
+
+        let mut string = Self::truncated(&text[..floor_char_boundary(text, N - ELLIPSIS.len())]);
+        let _ = string.0.push_str(ELLIPSIS);
+        string
+    }
+
+    /// Creates a [`String`] from formatting arguments.
+    ///
+    /// It is usually invoked through the [`format_string!`] macro.
+    ///
+    /// # Errors
+    ///
+    /// If the formatted text is greater than `N` bytes, an error is returned.
+    pub fn from_fmt(arguments: Arguments<'_>) -> Result<Self> {
+        let mut string = Self::empty();
+        core::fmt::Write::write_fmt(&mut string, arguments).map_err(|_| {
+            Error::new(
+                ErrorKind::FixedText,
+                "Impossible to format the text into a stack string.",
+            )
+        })?;
+        Ok(string)
+    }
+
+    /// Converts a [`String`] into a [`String`] with a different capacity.
+    ///
+    /// # Errors
+    ///
+    /// If the text is greater than `M` bytes, an error is returned.
+    pub fn resize<const M: usize>(&self) -> Result<String<M>> {
+        String::new(self.as_str())
+    }
+
+    /// Converts a [`String`] into a [`String`] with a different capacity,
+    /// truncating the text when it is greater than `M` bytes.
+    #[must_use]
+    pub fn resize_truncated<const M: usize>(&self) -> String<M> {
+        String::truncated(self.as_str())
+    }
+
+    /// Returns the [`String`] length in bytes.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns the [`String`] capacity in bytes.
+    #[must_use]
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+
+    /// Returns the number of bytes which can still be added to [`String`].
+    #[must_use]
+    pub fn remaining(&self) -> usize {
+        N - self.0.len()
+    }
+
     /// Checks whether a [`String`] is empty.
     #[must_use]
     pub fn is_empty(&self) -> bool {
@@ -90,6 +247,80 @@ Characters might not be UTF-8 or its length is wrong.",
     }
 }
 
+// Returns the greatest index not greater than `index` which lies on a
+// character boundary of the text.
+fn floor_char_boundary(text: &str, index: usize) -> usize {
+    if index >= text.len() {
+        return text.len();
+    }
+    (0..=index)
+        .rev()
+        .find(|index| text.is_char_boundary(*index))
+        .unwrap_or_default()
+}
+
 /// A fixed-capacity [`String`](https://doc.rust-lang.org/std/string/struct.String.html).
-#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Clone, Serialize, Deserialize)]
 pub struct String<const N: usize>(OtherString<N>);
+
+#[cfg(test)]
+mod tests {
+    use core::fmt::Write;
+
+    use super::{ELLIPSIS, String};
+
+    #[test]
+    fn test_truncated() {
+        assert_eq!(String::<8>::truncated("light"), "light");
+        assert_eq!(String::<8>::truncated("brightness"), "brightne");
+        // `è` is encoded with two bytes, so it cannot be split.
+        assert_eq!(String::<4>::truncated("perè"), "per");
+        assert_eq!(String::<0>::truncated("light"), "");
+
+        assert_eq!(String::<8>::truncated_with_ellipsis("light"), "light");
+        assert_eq!(
+            String::<8>::truncated_with_ellipsis("brightness"),
+            "brigh..."
+        );
+        assert_eq!(String::<6>::truncated_with_ellipsis("èèèè"), "è...");
+        assert_eq!(String::<2>::truncated_with_ellipsis("light"), "li");
+        assert_eq!(
+            String::<8>::truncated_with_ellipsis("brightness").len(),
+            5 + ELLIPSIS.len()
+        );
+    }
+
+    #[test]
+    fn test_format_string() {
+        let text: String<16> = format_string!("{} of {}", 3, "lights").unwrap();
+        assert_eq!(text, "3 of lights");
+        assert!(text.ends_with("lights"));
+
+        let mut text = text;
+        write!(text, "!").unwrap();
+        assert_eq!(text.as_ref(), "3 of lights!");
+
+        assert!(String::<4>::from_fmt(format_args!("{}", 12345)).is_err());
+    }
+
+    #[test]
+    fn test_capacity() {
+        let text = String::<16>::new("light").unwrap();
+        assert_eq!(text.len(), 5);
+        assert_eq!(text.capacity(), 16);
+        assert_eq!(text.remaining(), 11);
+
+        assert_eq!(text.resize::<5>().unwrap(), "light");
+        assert!(text.resize::<4>().is_err());
+        assert_eq!(text.resize_truncated::<4>(), "ligh");
+    }
+
+    #[test]
+    fn test_ordering() {
+        let first = String::<8>::new("alpha").unwrap();
+        let second: String<8> = "beta".parse().unwrap();
+        assert!(first < second);
+        assert_eq!(first.max(second.clone()), second);
+        assert!(String::<2>::try_from("light").is_err());
+    }
+}
@@ -0,0 +1,142 @@
+use core::fmt::{Display, Formatter};
+
+use serde::de::{Error as DeError, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::error::{Error, ErrorKind, Result};
+
+/// Version of the device description format produced by this crate.
+///
+/// It is serialized within [`DeviceData`](crate::device::DeviceData),
+/// so that a controller can detect a description format it cannot read.
+/// It changes on every change of the serialized format:
+///
+/// - `0.1.0`: initial format
+/// - `0.2.0`: adds the route `path`, the device `description` and the
+///   `mandatory_routes` fields, and the bounds of integer parameters
+pub const SCHEMA_VERSION: Version = Version::new(0, 2, 0);
+
+/// A semantic version in the `major.minor.patch` form.
+///
+/// It is serialized as a string, such as `"1.2.3"`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Version {
+    /// Major version, incremented on incompatible changes.
+    pub major: u16,
+    /// Minor version, incremented on backward compatible additions.
+    pub minor: u16,
+    /// Patch version, incremented on backward compatible fixes.
+    pub patch: u16,
+}
+
+impl Display for Version {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+impl Serialize for Version {
+    fn serialize<S: Serializer>(&self, serializer: S) -> core::result::Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Version {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> core::result::Result<Self, D::Error> {
+        struct VersionVisitor;
+
+        impl Visitor<'_> for VersionVisitor {
+            type Value = Version;
+
+            fn expecting(&self, formatter: &mut Formatter) -> core::fmt::Result {
+                formatter.write_str("a `major.minor.patch` version")
+            }
+
+            fn visit_str<E: DeError>(self, value: &str) -> core::result::Result<Version, E> {
+                Version::parse(value).map_err(|e| E::custom(e.info()))
+            }
+        }
+
+        deserializer.deserialize_str(VersionVisitor)
+    }
+}
+
+impl Version {
+    /// Creates a [`Version`].
+    #[must_use]
+    pub const fn new(major: u16, minor: u16, patch: u16) -> Self {
+        Self {
+            major,
+            minor,
+            patch,
+        }
+    }
+
+    /// Checks whether a [`Version`] is compatible with another one.
+    ///
+    /// Two versions are compatible when they have the same major version.
+    /// When the major version is `0`, the minor version must be the same too.
+    #[must_use]
+    pub const fn is_compatible_with(&self, other: &Self) -> bool {
+        self.major == other.major && (self.major != 0 || self.minor == other.minor)
+    }
+
+    /// Parses a [`Version`] from a `major.minor.patch` text.
+    ///
+    /// # Errors
+    ///
+    /// If the text is not in the `major.minor.patch` form, an error
+    /// is returned.
+    pub fn parse(text: &str) -> Result<Self> {
+        let mut numbers = text.trim().split('.').map(str::parse::<u16>);
+        match (
+            numbers.next(),
+            numbers.next(),
+            numbers.next(),
+            numbers.next(),
+        ) {
+            (Some(Ok(major)), Some(Ok(minor)), Some(Ok(patch)), None) => {
+                Ok(Self::new(major, minor, patch))
+            }
+            _ => Err(Error::new(
+                ErrorKind::Version,
+                "A version must be in the `major.minor.patch` form.",
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use crate::{deserialize, serialize};
+
+    use super::{SCHEMA_VERSION, Version};
+
+    #[test]
+    fn test_version() {
+        let version = Version::new(1, 12, 3);
+
+        assert_eq!(serialize(version), json!("1.12.3"));
+        assert_eq!(deserialize::<Version>(json!("1.12.3")), version);
+
+        assert_eq!(Version::parse("1.12.3").unwrap(), version);
+        assert!(Version::parse("1.12").is_err());
+        assert!(Version::parse("1.12.3.4").is_err());
+        assert!(Version::parse("1.a.3").is_err());
+
+        assert!(Version::new(1, 2, 0) > Version::new(1, 1, 9));
+    }
+
+    #[test]
+    fn test_compatible_versions() {
+        assert!(Version::new(1, 2, 3).is_compatible_with(&Version::new(1, 0, 0)));
+        assert!(!Version::new(2, 0, 0).is_compatible_with(&Version::new(1, 9, 9)));
+
+        assert!(Version::new(0, 1, 3).is_compatible_with(&Version::new(0, 1, 0)));
+        assert!(!Version::new(0, 2, 0).is_compatible_with(&Version::new(0, 1, 0)));
+
+        assert!(SCHEMA_VERSION.is_compatible_with(&SCHEMA_VERSION));
+    }
+}
@@ -0,0 +1,153 @@
+//! Memory budgets of representative `tosca-stack` structures.
+//!
+//! The stack size of each structure and the encoded size of a device
+//! description are compared with recorded budgets. A budget is exceeded
+//! when a change increases the memory footprint of the crate, so the
+//! budget must be consciously updated together with that change.
+//!
+//! When a budget is exceeded, the test fails with a report of all
+//! measurements.
+//!
+//! The binary size of a representative bare-metal device is checked
+//! separately by `scripts/binary_size.sh`.
+
+// Budgets are recorded for 64-bit targets only, since structure sizes
+// depend on the pointer width.
+#![cfg(target_pointer_width = "64")]
+
+use core::fmt::Write;
+use core::mem::size_of;
+
+use tosca_stack::collections::Linear;
+use tosca_stack::device::{DeviceData, DeviceEnvironment, DeviceInfo, DeviceKind};
+use tosca_stack::hazards::{Hazard, Hazards};
+use tosca_stack::parameters::Parameters;
+use tosca_stack::response::ErrorResponse;
+use tosca_stack::route::{Route, RouteConfig, RouteConfigs, RouteConfigsBuilder, Routes};
+use tosca_stack::string::String;
+
+// A measurement with its budget, both expressed in bytes.
+struct Measurement {
+    name: &'static str,
+    size: usize,
+    budget: usize,
+}
+
+impl Measurement {
+    const fn new(name: &'static str, size: usize, budget: usize) -> Self {
+        Self { name, size, budget }
+    }
+}
+
+fn device_data() -> DeviceData<4, 4, 4> {
+    let route_configs = RouteConfigsBuilder::<4, 4, 4>::new()
+        .route(
+            Route::put("/on")
+                .description("Turns the light on")
+                .with_hazards(
+                    Hazards::<4>::new()
+                        .insert(Hazard::FireHazard)
+                        .insert(Hazard::ElectricEnergyConsumption),
+                )
+                .with_parameters(
+                    Parameters::<4>::new()
+                        .rangef64("brightness", (0., 20., 0.1))
+                        .bool("save-energy", false),
+                ),
+        )
+        .unwrap()
+        .route(
+            Route::put("/off")
+                .description("Turns the light off")
+                .with_hazards(Hazards::new())
+                .with_parameters(Parameters::new()),
+        )
+        .unwrap()
+        .route(
+            Route::get("/state")
+                .description("Returns the light state")
+                .with_hazards(Hazards::new())
+                .with_parameters(Parameters::new()),
+        )
+        .unwrap()
+        .build();
+
+    DeviceData::new(
+        DeviceKind::Light,
+        DeviceEnvironment::Esp32,
+        "/light",
+        route_configs,
+    )
+}
+
+#[test]
+fn test_budgets() {
+    let budgets = [
+        Measurement::new("Route<2, 2>", size_of::<Route<2, 2>>(), 288),
+        Measurement::new("Route<4, 4>", size_of::<Route<4, 4>>(), 472),
+        Measurement::new("Routes<4, 4, 8>", size_of::<Routes<4, 4, 8>>(), 3880),
+        Measurement::new("Parameters<4>", size_of::<Parameters<4>>(), 344),
+        Measurement::new("Parameters<16>", size_of::<Parameters<16>>(), 1352),
+        Measurement::new("Hazards<8>", size_of::<Hazards<8>>(), 72),
+        Measurement::new("RouteConfig<4, 4>", size_of::<RouteConfig<4, 4>>(), 480),
+        Measurement::new(
+            "RouteConfigs<4, 4, 8>",
+            size_of::<RouteConfigs<4, 4, 8>>(),
+            3944,
+        ),
+        Measurement::new(
+            "DeviceData<4, 4, 8>",
+            size_of::<DeviceData<4, 4, 8>>(),
+            4064,
+        ),
+        Measurement::new("Route<0, 0, Linear>", size_of::<Route<0, 0, Linear>>(), 104),
+        Measurement::new(
+            "Routes<4, 4, 9, Linear>",
+            size_of::<Routes<4, 4, 9, Linear>>(),
+            3608,
+        ),
+        Measurement::new(
+            "Parameters<4, Linear>",
+            size_of::<Parameters<4, Linear>>(),
+            296,
+        ),
+        Measurement::new("Hazards<3, Linear>", size_of::<Hazards<3, Linear>>(), 16),
+        Measurement::new(
+            "DeviceData<4, 4, 8, 0, Linear>",
+            size_of::<DeviceData<4, 4, 8, 0, Linear>>(),
+            3392,
+        ),
+        Measurement::new(
+            "DeviceInfo<0, 0, 0, 0>",
+            size_of::<DeviceInfo<0, 0, 0, 0>>(),
+            112,
+        ),
+        Measurement::new(
+            "DeviceInfo<2, 2, 2, 2>",
+            size_of::<DeviceInfo<2, 2, 2, 2>>(),
+            192,
+        ),
+        Measurement::new("ErrorResponse<64>", size_of::<ErrorResponse<64>>(), 328),
+        Measurement::new("String<32>", size_of::<String<32>>(), 40),
+        Measurement::new(
+            "Encoded DeviceData<4, 4, 4>",
+            serde_json::to_vec(&device_data()).unwrap().len(),
+            646,
+        ),
+    ];
+
+    let exceeded = budgets
+        .iter()
+        .any(|Measurement { size, budget, .. }| size > budget);
+
+    let mut report = std::string::String::new();
+    for Measurement { name, size, budget } in &budgets {
+        let status = if size > budget { "EXCEEDED" } else { "ok" };
+        writeln!(report, "{name:<32} {size:>6} / {budget:>6} bytes  {status}").unwrap();
+    }
+
+    assert!(
+        !exceeded,
+        "At least one memory budget has been exceeded:\n{report}"
+    );
+}
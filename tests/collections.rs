@@ -0,0 +1,321 @@
+//! Property-based tests of the fixed-capacity collections.
+//!
+//! Each collection is compared with a simple model built on heap
+//! collections, which defines the expected semantics: elements are kept in
+//! insertion order, duplicates are discarded, and elements exceeding the
+//! capacity are silently dropped.
+//!
+//! Linear collections are also compared with hash-based collections, since
+//! both must behave and serialize in the same way. Maps are compared as JSON
+//! text, because a JSON value does not preserve the order of the keys.
+
+use proptest::collection::vec;
+use proptest::prelude::*;
+
+use tosca_stack::collections::{
+    LinearMap, LinearSet, Map, OutputLinearMap, OutputLinearSet, OutputMap, OutputSet,
+    SerialLinearMap, SerialLinearSet, SerialMap, SerialSet, Set,
+};
+
+const N: usize = 8;
+
+// Returns the elements stored by a set with `N` capacity.
+fn set_model(elements: &[u8]) -> Vec<u8> {
+    let mut model = Vec::new();
+    for element in elements {
+        if !model.contains(element) && model.len() < N {
+            model.push(*element);
+        }
+    }
+    model
+}
+
+// Returns all unique elements, regardless of the capacity.
+fn set_model_unbounded(elements: &[u8]) -> Vec<u8> {
+    let mut model = Vec::new();
+    for element in elements {
+        if !model.contains(element) {
+            model.push(*element);
+        }
+    }
+    model
+}
+
+// Returns the entries stored by a map with `N` capacity.
+//
+// An existing key has its value replaced, even when the map is full.
+fn map_model(entries: &[(u8, u16)]) -> Vec<(u8, u16)> {
+    let mut model: Vec<(u8, u16)> = Vec::new();
+    for (key, value) in entries {
+        if let Some(entry) = model.iter_mut().find(|(k, _)| k == key) {
+            entry.1 = *value;
+        } else if model.len() < N {
+            model.push((*key, *value));
+        }
+    }
+    model
+}
+
+proptest! {
+    #[test]
+    fn test_set_insertion(elements in vec(any::<u8>(), 0..32)) {
+        let model = set_model(&elements);
+
+        let mut set = Set::<u8, N>::new();
+        for element in &elements {
+            set.add(*element);
+        }
+
+        prop_assert!(set.len() <= N);
+        prop_assert_eq!(set.len(), model.len());
+        prop_assert_eq!(set.is_empty(), model.is_empty());
+        prop_assert_eq!(set.iter().copied().collect::<Vec<_>>(), model.clone());
+        for element in &elements {
+            prop_assert_eq!(set.contains(element), model.contains(element));
+        }
+
+        let serial_set = SerialSet::<u8, N>::init_with_elements(&elements);
+        prop_assert_eq!(serial_set.iter().copied().collect::<Vec<_>>(), model.clone());
+
+        let output_set = OutputSet::<u8, N>::from(set);
+        prop_assert_eq!(output_set.iter().copied().collect::<Vec<_>>(), model);
+    }
+
+    #[test]
+    fn test_set_merge(
+        first in vec(any::<u8>(), 0..16),
+        second in vec(any::<u8>(), 0..16),
+    ) {
+        let mut set = Set::<u8, N>::init_with_elements(&first);
+        set.merge(&Set::init_with_elements(&second));
+
+        let model = set_model(&[set_model(&first), set_model(&second)].concat());
+        prop_assert_eq!(set.iter().copied().collect::<Vec<_>>(), model);
+    }
+
+    #[test]
+    fn test_map_insertion(entries in vec((any::<u8>(), any::<u16>()), 0..32)) {
+        let model = map_model(&entries);
+
+        let mut map = Map::<u8, u16, N>::new();
+        for (key, value) in &entries {
+            map.add(*key, *value);
+        }
+
+        prop_assert!(map.len() <= N);
+        prop_assert_eq!(map.len(), model.len());
+        prop_assert_eq!(map.is_empty(), model.is_empty());
+        prop_assert_eq!(
+            map.iter().map(|(k, v)| (*k, *v)).collect::<Vec<_>>(),
+            model.clone()
+        );
+        for (key, _) in &entries {
+            prop_assert_eq!(
+                map.contains_key(key),
+                model.iter().any(|(k, _)| k == key)
+            );
+        }
+
+        let serial_map = SerialMap::<u8, u16, N>::init_with_elements(&entries);
+        prop_assert_eq!(
+            serial_map.iter().map(|(k, v)| (*k, *v)).collect::<Vec<_>>(),
+            model.clone()
+        );
+
+        let output_map = OutputMap::<u8, u16, N>::from(map);
+        prop_assert_eq!(
+            output_map.iter().map(|(k, v)| (*k, *v)).collect::<Vec<_>>(),
+            model
+        );
+    }
+
+    #[test]
+    fn test_set_removal(
+        elements in vec(any::<u8>(), 0..16),
+        removed in vec(any::<u8>(), 0..8),
+    ) {
+        let mut model = set_model(&elements);
+        let mut set = Set::<u8, N>::init_with_elements(&elements);
+
+        for element in &removed {
+            prop_assert_eq!(set.remove(element), model.contains(element));
+            model.retain(|stored| stored != element);
+        }
+        prop_assert_eq!(set.iter().copied().collect::<Vec<_>>(), model.clone());
+
+        set.retain(|element| element % 2 == 0);
+        model.retain(|element| element % 2 == 0);
+        prop_assert_eq!(set.iter().copied().collect::<Vec<_>>(), model.clone());
+        for element in &elements {
+            prop_assert_eq!(set.get(element), model.iter().find(|stored| *stored == element));
+        }
+
+        set.clear();
+        prop_assert!(set.is_empty());
+    }
+
+    #[test]
+    fn test_set_overflow(elements in vec(any::<u8>(), 0..16)) {
+        let model = set_model(&elements);
+        let unique = set_model_unbounded(&elements);
+
+        let set = Set::<u8, N>::try_from_iter(elements.iter().copied());
+        prop_assert_eq!(set.is_ok(), unique.len() <= N);
+
+        let set = elements.iter().copied().collect::<Set<u8, N>>();
+        prop_assert_eq!(set.iter().copied().collect::<Vec<_>>(), model);
+    }
+
+    #[test]
+    fn test_map_removal(
+        entries in vec((any::<u8>(), any::<u16>()), 0..16),
+        removed in vec(any::<u8>(), 0..8),
+    ) {
+        let mut model = map_model(&entries);
+        let mut map = Map::<u8, u16, N>::init_with_elements(&entries);
+
+        for key in &removed {
+            let value = model.iter().find(|(k, _)| k == key).map(|(_, v)| *v);
+            prop_assert_eq!(map.remove(key), value);
+            model.retain(|(k, _)| k != key);
+        }
+        prop_assert_eq!(
+            map.iter().map(|(k, v)| (*k, *v)).collect::<Vec<_>>(),
+            model.clone()
+        );
+
+        for (key, value) in &mut model {
+            let stored = map.get_mut(key).unwrap();
+            *stored = stored.wrapping_add(1);
+            *value = value.wrapping_add(1);
+        }
+        map.retain(|key, _| key % 2 == 0);
+        model.retain(|(key, _)| key % 2 == 0);
+        for (key, _) in &entries {
+            prop_assert_eq!(
+                map.get(key),
+                model.iter().find(|(k, _)| k == key).map(|(_, v)| v)
+            );
+        }
+
+        map.clear();
+        prop_assert!(map.is_empty());
+    }
+
+    #[test]
+    fn test_map_overflow(entries in vec((any::<u8>(), any::<u16>()), 0..16)) {
+        let model = map_model(&entries);
+        let keys = set_model_unbounded(&entries.iter().map(|(k, _)| *k).collect::<Vec<_>>());
+
+        let map = Map::<u8, u16, N>::try_from_iter(entries.iter().copied());
+        prop_assert_eq!(map.is_ok(), keys.len() <= N);
+
+        let map = entries.iter().copied().collect::<Map<u8, u16, N>>();
+        prop_assert_eq!(
+            map.iter().map(|(k, v)| (*k, *v)).collect::<Vec<_>>(),
+            model
+        );
+    }
+
+    #[test]
+    fn test_linear_set_equivalence(
+        elements in vec(any::<u8>(), 0..16),
+        removed in vec(any::<u8>(), 0..8),
+    ) {
+        let mut set = Set::<u8, N>::init_with_elements(&elements);
+        let mut linear_set = LinearSet::<u8, N>::init_with_elements(&elements);
+
+        for element in &removed {
+            prop_assert_eq!(linear_set.remove(element), set.remove(element));
+        }
+        prop_assert_eq!(
+            linear_set.iter().collect::<Vec<_>>(),
+            set.iter().collect::<Vec<_>>()
+        );
+        prop_assert_eq!(
+            LinearSet::<u8, N>::try_from_iter(elements.iter().copied()).is_ok(),
+            Set::<u8, N>::try_from_iter(elements.iter().copied()).is_ok()
+        );
+
+        let serial_set = SerialSet::<u8, N>::init_with_elements(&elements);
+        let serial_linear_set = SerialLinearSet::<u8, N>::init_with_elements(&elements);
+        prop_assert_eq!(
+            serde_json::to_value(serial_linear_set).unwrap(),
+            serde_json::to_value(serial_set).unwrap()
+        );
+
+        let output_set = OutputSet::<u8, N>::from(set);
+        let output_linear_set = OutputLinearSet::<u8, N>::from(linear_set);
+        let value = serde_json::to_value(&output_linear_set).unwrap();
+        prop_assert_eq!(&value, &serde_json::to_value(output_set).unwrap());
+        prop_assert_eq!(
+            output_linear_set.iter().rev().copied().collect::<OutputLinearSet<u8, N>>(),
+            output_linear_set.clone()
+        );
+        prop_assert_eq!(
+            serde_json::from_value::<OutputLinearSet<u8, N>>(value).unwrap(),
+            output_linear_set
+        );
+
+        let json = serde_json::to_value(&elements).unwrap();
+        prop_assert_eq!(
+            serde_json::from_value::<OutputLinearSet<u8, N>>(json.clone()).ok(),
+            serde_json::from_value::<OutputSet<u8, N>>(json)
+                .ok()
+                .map(|set| set.iter().copied().collect::<OutputLinearSet<u8, N>>())
+        );
+    }
+
+    #[test]
+    fn test_linear_map_equivalence(
+        entries in vec((any::<u8>(), any::<u16>()), 0..16),
+        removed in vec(any::<u8>(), 0..8),
+    ) {
+        let mut map = Map::<u8, u16, N>::init_with_elements(&entries);
+        let mut linear_map = LinearMap::<u8, u16, N>::init_with_elements(&entries);
+
+        for key in &removed {
+            prop_assert_eq!(linear_map.remove(key), map.remove(key));
+        }
+        prop_assert_eq!(
+            linear_map.iter().collect::<Vec<_>>(),
+            map.iter().collect::<Vec<_>>()
+        );
+        prop_assert_eq!(
+            LinearMap::<u8, u16, N>::try_from_iter(entries.iter().copied()).is_ok(),
+            Map::<u8, u16, N>::try_from_iter(entries.iter().copied()).is_ok()
+        );
+
+        let serial_map = SerialMap::<u8, u16, N>::init_with_elements(&entries);
+        let serial_linear_map = SerialLinearMap::<u8, u16, N>::init_with_elements(&entries);
+        prop_assert_eq!(
+            serde_json::to_string(&serial_linear_map).unwrap(),
+            serde_json::to_string(&serial_map).unwrap()
+        );
+
+        let output_map = OutputMap::<u8, u16, N>::from(map);
+        let output_linear_map = OutputLinearMap::<u8, u16, N>::from(linear_map);
+        let value = serde_json::to_string(&output_linear_map).unwrap();
+        prop_assert_eq!(&value, &serde_json::to_string(&output_map).unwrap());
+
+        let output_linear_map = serde_json::from_str::<OutputLinearMap<u8, u16, N>>(&value).unwrap();
+        prop_assert_eq!(
+            output_linear_map.iter().collect::<Vec<_>>(),
+            output_map.iter().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_linear_capacity(elements in vec(any::<u8>(), 0..32)) {
+        const CAPACITY: usize = 9;
+
+        let linear_set = LinearSet::<u8, CAPACITY>::init_with_elements(&elements);
+        let unique = set_model_unbounded(&elements);
+
+        prop_assert_eq!(linear_set.len(), unique.len().min(CAPACITY));
+        prop_assert_eq!(
+            linear_set.iter().copied().collect::<Vec<_>>(),
+            unique[..unique.len().min(CAPACITY)].to_vec()
+        );
+    }
+}
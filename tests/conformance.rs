@@ -0,0 +1,412 @@
+//! Conformance tests between `tosca-stack` structures and the heap-based
+//! `tosca` structures consumed by a controller.
+//!
+//! Each stack structure is serialized and then deserialized with the
+//! corresponding `tosca` structure. Serializing the `tosca` structure again
+//! must produce the same output, otherwise some data has been lost or
+//! misread. When a stack structure can also be deserialized, the `tosca`
+//! output is deserialized back and compared with the original structure.
+
+use core::fmt::Debug;
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+use tosca_stack::access::AccessLevel;
+use tosca_stack::device::{
+    DeviceData, DeviceDescriptor, DeviceEnvironment, DeviceIdentity, DeviceInfo, DeviceKind,
+};
+use tosca_stack::economy::{Cost, CostTimespan, Costs, Economy, Roi, Rois};
+use tosca_stack::energy::{
+    CarbonFootprint, CarbonFootprints, Energy, EnergyClass, EnergyEfficiencies, EnergyEfficiency,
+    WaterUseEfficiency,
+};
+use tosca_stack::events::{Event, EventQueue, Severity};
+use tosca_stack::hazards::{Hazard, Hazards};
+use tosca_stack::metering::{EnergyMeter, EnergyReport};
+use tosca_stack::parameters::{ParameterDescriptor, Parameters};
+use tosca_stack::rate_limit::RateLimit;
+use tosca_stack::response::{EnergyResponse, ErrorKind, ErrorResponse, InfoResponse};
+use tosca_stack::route::{Route, RouteConfigsBuilder, RouteDescriptor};
+use tosca_stack::string::String;
+use tosca_stack::version::Version;
+
+// Fields added by `tosca-stack` which are not part of the `tosca` structures.
+const ROUTE_EXTENSIONS: &[&str] = &[
+    "descriptions",
+    "parameters descriptions",
+    "energy per invocation",
+    "cost per invocation",
+    "access level",
+    "rate limit",
+];
+const DEVICE_DATA_EXTENSIONS: &[&str] = &["schema version"];
+const DEVICE_DATA_ALL_EXTENSIONS: &[&str] = &[
+    "schema version",
+    "identity",
+    "descriptions",
+    "parameters descriptions",
+    "energy per invocation",
+    "cost per invocation",
+    "access level",
+    "rate limit",
+];
+const ERROR_RESPONSE_EXTENSIONS: &[&str] = &["code", "route", "parameter", "retry after"];
+const EVENT_EXTENSIONS: &[&str] = &["id", "timestamp", "route", "hazards", "severity", "payload"];
+
+// Serializes a stack structure, deserializes it as a `tosca` structure and
+// checks whether both structures produce the same output.
+//
+// The `extensions` fields are ignored by the comparison, at any depth, and
+// each of them must be present, so that no other field is ignored.
+fn assert_conformance<Stack, Heap>(stack: &Stack, extensions: &[&str]) -> Value
+where
+    Stack: Serialize,
+    Heap: Serialize + DeserializeOwned,
+{
+    let mut stack_value = serde_json::to_value(stack).unwrap();
+    let heap: Heap = serde_json::from_value(stack_value.clone()).unwrap();
+    let heap_value = serde_json::to_value(heap).unwrap();
+
+    for extension in extensions {
+        assert!(
+            remove_extension(&mut stack_value, extension),
+            "missing `{extension}` extension"
+        );
+    }
+    assert_eq!(stack_value, heap_value);
+
+    heap_value
+}
+
+// Removes an extension field at any depth, returning whether it was found.
+fn remove_extension(value: &mut Value, extension: &str) -> bool {
+    match value {
+        Value::Object(map) => {
+            let mut found = map.remove(extension).is_some();
+            for value in map.values_mut() {
+                found |= remove_extension(value, extension);
+            }
+            found
+        }
+        Value::Array(values) => values.iter_mut().fold(false, |found, value| {
+            remove_extension(value, extension) | found
+        }),
+        _ => false,
+    }
+}
+
+// Checks the conformance of a stack structure and deserializes the `tosca`
+// output back into the stack structure.
+fn assert_round_trip<Stack, Heap>(stack: &Stack)
+where
+    Stack: Serialize + DeserializeOwned + PartialEq + Debug,
+    Heap: Serialize + DeserializeOwned,
+{
+    let heap_value = assert_conformance::<Stack, Heap>(stack, &[]);
+    assert_eq!(&serde_json::from_value::<Stack>(heap_value).unwrap(), stack);
+}
+
+fn economy() -> Economy<2, 2> {
+    Economy::<2, 2>::empty()
+        .costs(
+            Costs::<2>::new()
+                .insert(Cost::new(100, CostTimespan::Week))
+                .insert(Cost::new(-50, CostTimespan::Year)),
+        )
+        .rois(Rois::<2>::init(Roi::new(10, EnergyClass::A)))
+}
+
+fn energy() -> Energy<2, 2> {
+    Energy::<2, 2>::empty()
+        .energy_efficiencies(EnergyEfficiencies::<2>::init(EnergyEfficiency::new(
+            -50,
+            EnergyClass::B,
+        )))
+        .carbon_footprints(CarbonFootprints::<2>::init(CarbonFootprint::new(
+            20,
+            EnergyClass::C,
+        )))
+        .water_use_efficiency(WaterUseEfficiency {
+            gpp: Some(2.5),
+            penman_monteith_equation: None,
+            wer: Some(1.5),
+        })
+}
+
+fn route() -> Route<4, 4> {
+    Route::put("/on")
+        .description("Turns the light on")
+        .with_hazards(
+            Hazards::<4>::new()
+                .insert(Hazard::FireHazard)
+                .insert(Hazard::ElectricEnergyConsumption),
+        )
+        .with_parameters(
+            Parameters::<4>::new()
+                .bool("save-energy", false)
+                .u8("mode", 1)
+                .rangeu64_with_default("brightness", (0, 20, 1), 5)
+                .rangef64("temperature", (0., 40., 0.5)),
+        )
+}
+
+// A route using all extensions.
+fn extended_route() -> Route<4, 4> {
+    let route = route();
+    let parameters = route
+        .parameters()
+        .clone()
+        .localized_descriptions("brightness", &[("en", "Brightness"), ("it", "Luminosità")]);
+    route
+        .localized_descriptions(&[("en", "Turns the light on"), ("it", "Accende la luce")])
+        .with_parameters(parameters)
+        .with_energy(2.5)
+        .with_cost(0.5)
+        .with_access_level(AccessLevel::Administrator)
+        .with_rate_limit(RateLimit::cooldown(1000))
+}
+
+#[test]
+fn test_route_config() {
+    assert_conformance::<_, tosca::route::RouteConfig>(&route().serialize_data(), &[]);
+    assert_conformance::<_, tosca::route::RouteConfig>(
+        &Route::<2, 2>::get("/off").serialize_data(),
+        &[],
+    );
+}
+
+#[test]
+fn test_route_config_extensions() {
+    // Each extension on its own.
+    assert_conformance::<_, tosca::route::RouteConfig>(
+        &route()
+            .localized_descriptions(&[("en", "Turns the light on")])
+            .serialize_data(),
+        &["descriptions"],
+    );
+    assert_conformance::<_, tosca::route::RouteConfig>(
+        &route()
+            .with_parameters(
+                Parameters::<2>::new()
+                    .u8("mode", 1)
+                    .localized_descriptions("mode", &[("en", "Mode")]),
+            )
+            .serialize_data(),
+        &["parameters descriptions"],
+    );
+    assert_conformance::<_, tosca::route::RouteConfig>(
+        &route().with_energy(2.5).serialize_data(),
+        &["energy per invocation"],
+    );
+    assert_conformance::<_, tosca::route::RouteConfig>(
+        &route().with_cost(0.5).serialize_data(),
+        &["cost per invocation"],
+    );
+    assert_conformance::<_, tosca::route::RouteConfig>(
+        &route()
+            .with_access_level(AccessLevel::Administrator)
+            .serialize_data(),
+        &["access level"],
+    );
+    assert_conformance::<_, tosca::route::RouteConfig>(
+        &route()
+            .with_rate_limit(RateLimit::cooldown(1000))
+            .serialize_data(),
+        &["rate limit"],
+    );
+
+    // All extensions together.
+    assert_conformance::<_, tosca::route::RouteConfig>(
+        &extended_route().serialize_data(),
+        ROUTE_EXTENSIONS,
+    );
+}
+
+#[test]
+fn test_device_data() {
+    let route_configs = RouteConfigsBuilder::<4, 4, 2>::new()
+        .route(route())
+        .unwrap()
+        .route(
+            Route::delete("/on")
+                .with_hazards(Hazards::new())
+                .with_parameters(Parameters::new()),
+        )
+        .unwrap()
+        .build();
+
+    assert_conformance::<_, tosca::device::DeviceData>(
+        &DeviceData::new(
+            DeviceKind::Light,
+            DeviceEnvironment::Esp32,
+            "/light",
+            route_configs,
+        ),
+        DEVICE_DATA_EXTENSIONS,
+    );
+}
+
+#[test]
+fn test_device_data_extensions() {
+    let route_configs = RouteConfigsBuilder::<4, 4, 2>::new()
+        .route(extended_route())
+        .unwrap()
+        .route(
+            Route::delete("/on")
+                .with_hazards(Hazards::new())
+                .with_parameters(Parameters::new()),
+        )
+        .unwrap()
+        .build();
+
+    assert_conformance::<_, tosca::device::DeviceData>(
+        &DeviceData::new(
+            DeviceKind::Light,
+            DeviceEnvironment::Esp32,
+            "/light",
+            route_configs,
+        )
+        .identity(
+            DeviceIdentity::<16>::empty()
+                .serial_number(String::infallible("SN-0001"))
+                .firmware_version(Version::new(1, 2, 3)),
+        ),
+        DEVICE_DATA_ALL_EXTENSIONS,
+    );
+}
+
+#[test]
+fn test_device_descriptor() {
+    static DEVICE: DeviceDescriptor = DeviceDescriptor::new(
+        DeviceKind::Light,
+        DeviceEnvironment::Esp32,
+        "/light",
+        &[
+            RouteDescriptor::put("/on")
+                .description("Turns the light on")
+                .with_hazards(&[Hazard::FireHazard, Hazard::ElectricEnergyConsumption])
+                .with_parameters(&[
+                    ParameterDescriptor::bool("save-energy", false),
+                    ParameterDescriptor::u8("mode", 1),
+                    ParameterDescriptor::rangeu64_with_default("brightness", (0, 20, 1), 5),
+                    ParameterDescriptor::rangef64("temperature", (0., 40., 0.5)),
+                ]),
+            RouteDescriptor::delete("/on"),
+        ],
+    );
+
+    assert_conformance::<_, tosca::device::DeviceData>(&DEVICE, DEVICE_DATA_EXTENSIONS);
+}
+
+#[test]
+fn test_economy() {
+    assert_round_trip::<_, tosca::economy::Economy>(&economy());
+    assert_round_trip::<_, tosca::economy::Economy>(&Economy::<2, 2>::empty());
+}
+
+#[test]
+fn test_energy() {
+    assert_round_trip::<_, tosca::energy::Energy>(&energy());
+    assert_round_trip::<_, tosca::energy::Energy>(&Energy::<2, 2>::empty());
+}
+
+#[test]
+fn test_device_info() {
+    let device_info = DeviceInfo::empty()
+        .add_economy(economy())
+        .add_energy(energy());
+
+    assert_round_trip::<_, tosca::device::DeviceInfo>(&device_info);
+    assert_round_trip::<_, tosca::device::DeviceInfo>(&DeviceInfo::new(
+        Economy::<2, 2>::empty(),
+        Energy::<2, 2>::empty(),
+    ));
+}
+
+#[test]
+fn test_info_response() {
+    let device_info = DeviceInfo::empty()
+        .add_economy(economy())
+        .add_energy(energy());
+
+    assert_round_trip::<_, tosca::response::InfoResponse>(&InfoResponse::new(device_info));
+}
+
+#[test]
+fn test_energy_response() {
+    let energy = energy();
+    let mut meter = EnergyMeter::<4>::new(3600);
+    meter.sample_power(0, 100.);
+    meter.sample_power(5400, 100.);
+
+    // Measurements are ignored by a controller reading the energy
+    // information of a device.
+    let response =
+        serde_json::to_value(EnergyResponse::new(EnergyReport::new(&energy, &meter))).unwrap();
+    let heap: tosca::response::InfoResponse = serde_json::from_value(response).unwrap();
+
+    assert_eq!(
+        serde_json::to_value(heap).unwrap(),
+        serde_json::to_value(InfoResponse::new(DeviceInfo::empty().add_energy(energy))).unwrap()
+    );
+}
+
+#[test]
+fn test_error_response() {
+    assert_round_trip::<_, tosca::response::ErrorResponse>(&ErrorResponse::<64>::invalid_data(
+        "Invalid brightness value",
+    ));
+    assert_round_trip::<_, tosca::response::ErrorResponse>(
+        &ErrorResponse::<64>::with_description_error(
+            ErrorKind::Internal,
+            "Sensor failure",
+            "Temperature sensor not responding",
+        ),
+    );
+}
+
+#[test]
+fn test_error_response_extensions() {
+    assert_conformance::<_, tosca::response::ErrorResponse>(
+        &ErrorResponse::<64>::internal("Device busy")
+            .code(42)
+            .route("/light/on")
+            .parameter("brightness")
+            .retry_after(5),
+        ERROR_RESPONSE_EXTENSIONS,
+    );
+}
+
+#[test]
+fn test_event() {
+    let event = Event::<2, 16>::hazard("temperature", 95.5f32, 120, Hazard::FireHazard)
+        .unwrap()
+        .description("Heater temperature")
+        .with_hazard(Hazard::ElectricEnergyConsumption)
+        .unwrap()
+        .with_route("/heater")
+        .with_payload("Over temperature");
+    let value = assert_conformance::<_, tosca::events::Event<f32>>(&event, EVENT_EXTENSIONS);
+    assert_eq!(value["value"], 95.5);
+
+    // Events without the optional extensions and with any value type are
+    // still `tosca` events.
+    assert_conformance::<_, tosca::events::Event<bool>>(
+        &Event::<2, 16>::new("door", true, 130, Severity::Info),
+        &["id", "timestamp", "severity"],
+    );
+    assert_conformance::<_, tosca::events::Event<u8>>(
+        &Event::<2, 16>::new("battery", 8u8, 140, Severity::Warning),
+        &["id", "timestamp", "severity"],
+    );
+
+    // Queued events are `tosca` events as well.
+    let mut queue = EventQueue::<2, 16, 2>::new();
+    queue.raise(event);
+    let value = serde_json::to_value(&queue).unwrap();
+    let event: tosca::events::Event<f32> =
+        serde_json::from_value(value["events"][0].clone()).unwrap();
+    assert_eq!(event.name, "temperature");
+}
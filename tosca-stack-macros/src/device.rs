@@ -0,0 +1,306 @@
+use proc_macro2::TokenStream;
+
+use quote::{quote, quote_spanned};
+
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{Ident, LitStr, Result, Token, braced, bracketed, parenthesized};
+
+use crate::capacity;
+
+// A `builder(arguments)` parameter definition.
+struct Parameter {
+    builder: Ident,
+    arguments: TokenStream,
+}
+
+impl Parse for Parameter {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let builder = input.parse()?;
+        let content;
+        parenthesized!(content in input);
+        Ok(Self {
+            builder,
+            arguments: content.parse()?,
+        })
+    }
+}
+
+// A `method "/route" { ... }` route definition.
+struct RouteDefinition {
+    method: Ident,
+    name: LitStr,
+    description: Option<LitStr>,
+    hazards: Vec<Ident>,
+    parameters: Vec<Parameter>,
+}
+
+impl Parse for RouteDefinition {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let method: Ident = input.parse()?;
+        if !matches!(
+            method.to_string().as_str(),
+            "get" | "put" | "post" | "delete"
+        ) {
+            return Err(syn::Error::new(
+                method.span(),
+                "expected one of: `get`, `put`, `post`, `delete`",
+            ));
+        }
+
+        let mut route = Self {
+            method,
+            name: input.parse()?,
+            description: None,
+            hazards: Vec::new(),
+            parameters: Vec::new(),
+        };
+
+        if !input.peek(syn::token::Brace) {
+            return Ok(route);
+        }
+
+        let content;
+        braced!(content in input);
+        while !content.is_empty() {
+            let field: Ident = content.parse()?;
+            content.parse::<Token![:]>()?;
+            match field.to_string().as_str() {
+                "description" => route.description = Some(content.parse()?),
+                "hazards" => {
+                    let hazards;
+                    bracketed!(hazards in content);
+                    route.hazards = Punctuated::<Ident, Token![,]>::parse_terminated(&hazards)?
+                        .into_iter()
+                        .collect();
+                }
+                "parameters" => {
+                    let parameters;
+                    bracketed!(parameters in content);
+                    route.parameters =
+                        Punctuated::<Parameter, Token![,]>::parse_terminated(&parameters)?
+                            .into_iter()
+                            .collect();
+                }
+                _ => {
+                    return Err(syn::Error::new(
+                        field.span(),
+                        "expected one of: `description`, `hazards`, `parameters`",
+                    ));
+                }
+            }
+            if content.is_empty() {
+                break;
+            }
+            content.parse::<Token![,]>()?;
+        }
+
+        Ok(route)
+    }
+}
+
+// The whole device definition.
+pub(crate) struct Device {
+    kind: Ident,
+    environment: Ident,
+    main_route: LitStr,
+    routes: Vec<RouteDefinition>,
+}
+
+impl Parse for Device {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut kind = None;
+        let mut environment = None;
+        let mut main_route = None;
+        let mut routes = Vec::new();
+
+        while !input.is_empty() {
+            let field: Ident = input.parse()?;
+            input.parse::<Token![:]>()?;
+            match field.to_string().as_str() {
+                "kind" => kind = Some(input.parse()?),
+                "environment" => environment = Some(input.parse()?),
+                "main_route" => main_route = Some(input.parse()?),
+                "routes" => {
+                    let content;
+                    braced!(content in input);
+                    routes = Punctuated::<RouteDefinition, Token![,]>::parse_terminated(&content)?
+                        .into_iter()
+                        .collect();
+                }
+                _ => {
+                    return Err(syn::Error::new(
+                        field.span(),
+                        "expected one of: `kind`, `environment`, `main_route`, `routes`",
+                    ));
+                }
+            }
+            if input.is_empty() {
+                break;
+            }
+            input.parse::<Token![,]>()?;
+        }
+
+        let missing = |name| syn::Error::new(input.span(), format!("missing `{name}` field"));
+        Ok(Self {
+            kind: kind.ok_or_else(|| missing("kind"))?,
+            environment: environment.ok_or_else(|| missing("environment"))?,
+            main_route: main_route.ok_or_else(|| missing("main_route"))?,
+            routes,
+        })
+    }
+}
+
+pub(crate) fn expand(device: &Device) -> TokenStream {
+    let n = capacity(device.routes.len());
+    let h = capacity(
+        device
+            .routes
+            .iter()
+            .map(|route| route.hazards.len())
+            .max()
+            .unwrap_or_default(),
+    );
+    let p = capacity(
+        device
+            .routes
+            .iter()
+            .map(|route| route.parameters.len())
+            .max()
+            .unwrap_or_default(),
+    );
+
+    let routes = device.routes.iter().map(|route| {
+        let RouteDefinition {
+            method,
+            name,
+            description,
+            hazards,
+            parameters,
+        } = route;
+        let description = description
+            .as_ref()
+            .map(|description| quote!(.description(#description)));
+        let builders = parameters
+            .iter()
+            .map(|Parameter { builder, arguments }| quote!(.#builder(#arguments)));
+        quote! {
+            .insert(
+                ::tosca_stack::route::Route::#method(#name)
+                    #description
+                    .with_hazards(
+                        ::tosca_stack::hazards::Hazards::<#h>::new()
+                            #(.insert(::tosca_stack::hazards::Hazard::#hazards))*
+                    )
+                    .with_parameters(
+                        ::tosca_stack::parameters::Parameters::<#p>::new() #(#builders)*
+                    )
+            )
+        }
+    });
+
+    let checks = checks(device);
+
+    let Device {
+        kind,
+        environment,
+        main_route,
+        ..
+    } = device;
+
+    quote! {
+        {
+            #checks
+            let routes = ::tosca_stack::route::Routes::<#h, #p, #n>::new() #(#routes)*;
+            (
+                ::tosca_stack::device::DeviceData::new(
+                    ::tosca_stack::device::DeviceKind::#kind,
+                    ::tosca_stack::device::DeviceEnvironment::#environment,
+                    #main_route,
+                    routes.serialize_data(),
+                ),
+                routes,
+            )
+        }
+    }
+}
+
+pub(crate) fn expand_descriptor(device: &Device) -> TokenStream {
+    let routes = device.routes.iter().map(|route| {
+        let RouteDefinition {
+            method,
+            name,
+            description,
+            hazards,
+            parameters,
+        } = route;
+        let description = description
+            .as_ref()
+            .map(|description| quote!(.description(#description)));
+        let parameters = parameters.iter().map(|Parameter { builder, arguments }| {
+            quote!(::tosca_stack::parameters::ParameterDescriptor::#builder(#arguments))
+        });
+        quote! {
+            ::tosca_stack::route::RouteDescriptor::#method(#name)
+                #description
+                .with_hazards(&[#(::tosca_stack::hazards::Hazard::#hazards),*])
+                .with_parameters(&[#(#parameters),*])
+        }
+    });
+
+    let checks = checks(device);
+
+    let Device {
+        kind,
+        environment,
+        main_route,
+        ..
+    } = device;
+
+    quote! {
+        {
+            #checks
+            ::tosca_stack::device::DeviceDescriptor::new(
+                ::tosca_stack::device::DeviceKind::#kind,
+                ::tosca_stack::device::DeviceEnvironment::#environment,
+                #main_route,
+                &[#(#routes),*],
+            )
+        }
+    }
+}
+
+// Checks each route against the previous ones at compile time, through the
+// same function used by the `RouteConfigsBuilder`. Each check is spanned on
+// its route name, so an error points at the offending route.
+fn checks(device: &Device) -> TokenStream {
+    let routes = device
+        .routes
+        .iter()
+        .map(|RouteDefinition { method, name, .. }| {
+            let rest_kind = Ident::new(
+                match method.to_string().as_str() {
+                    "get" => "Get",
+                    "put" => "Put",
+                    "post" => "Post",
+                    _ => "Delete",
+                },
+                method.span(),
+            );
+            quote!((#name, ::tosca_stack::route::RestKind::#rest_kind))
+        })
+        .collect::<Vec<_>>();
+
+    let checks = device.routes.iter().enumerate().map(|(index, route)| {
+        let routes = &routes[..=index];
+        quote_spanned! {route.name.span()=>
+            const _: () = if let ::core::result::Result::Err(error) =
+                ::tosca_stack::route::check_routes(&[#(#routes),*])
+            {
+                ::core::panic!("{}", error.info());
+            };
+        }
+    });
+
+    quote!(#(#checks)*)
+}
@@ -0,0 +1,116 @@
+//! Macros for the `tosca-stack` crate.
+//!
+//! These macros generate the fixed-capacity structures of the `tosca-stack`
+//! crate from declarative Rust code, computing all of their capacities at
+//! compile time.
+//!
+//! This crate should **not** be used directly, but only through the
+//! re-exports of the `tosca-stack` crate.
+
+#![forbid(unsafe_code)]
+#![deny(missing_docs)]
+
+use proc_macro::TokenStream;
+
+use syn::{DeriveInput, parse_macro_input};
+
+// Device definition macro.
+mod device;
+// Route input parameters derive macro.
+mod parameters;
+
+/// Derives the `ToscaParameters` trait for a structure with named fields.
+///
+/// Each field represents a route input parameter, named as the field itself.
+/// The supported field types are [`bool`], [`u8`], [`u16`], [`u32`], [`u64`],
+/// [`f32`], and [`f64`].
+///
+/// A field can be customized through the `tosca` attribute:
+///
+/// - `rename = "name"` changes the parameter name
+/// - `default = value` sets the parameter default value
+/// - `range(min, max, step)` turns an [`u64`] or a [`f64`] field into a
+///   range parameter
+///
+/// ```ignore
+/// #[derive(ToscaParameters)]
+/// struct Light {
+///     on: bool,
+///     #[tosca(range(0., 20., 0.1), default = 5.)]
+///     brightness: f64,
+/// }
+/// ```
+#[proc_macro_derive(ToscaParameters, attributes(tosca))]
+pub fn derive_tosca_parameters(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    parameters::expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Defines a device and all of its routes.
+///
+/// It evaluates to a `(DeviceData, Routes)` tuple at runtime, where all
+/// capacities are computed from the device definition. The device data
+/// describes the device to a controller, while the route table can be used to
+/// run the device operations. A device description stored in a `static` item
+/// is defined through [`device_descriptor!`].
+///
+/// Defining the same route twice with the same method is a compile error.
+///
+/// ```ignore
+/// let (device_data, routes) = device! {
+///     kind: Light,
+///     environment: Esp32,
+///     main_route: "/light",
+///     routes: {
+///         put "/on" {
+///             description: "Turns the light on",
+///             hazards: [FireHazard, ElectricEnergyConsumption],
+///             parameters: [rangef64("brightness", (0., 20., 0.1))],
+///         },
+///         put "/off",
+///     },
+/// };
+/// ```
+#[proc_macro]
+pub fn device(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as device::Device);
+    device::expand(&input).into()
+}
+
+/// Defines a device and all of its routes as a constant expression.
+///
+/// It accepts the same definition of [`device!`] and evaluates to a
+/// `DeviceDescriptor`, so it can initialize a `static` item stored in
+/// read-only memory.
+///
+/// Defining the same route twice with the same method is a compile error.
+///
+/// ```ignore
+/// static DEVICE: DeviceDescriptor = device_descriptor! {
+///     kind: Light,
+///     environment: Esp32,
+///     main_route: "/light",
+///     routes: {
+///         put "/on" {
+///             hazards: [FireHazard],
+///             parameters: [rangef64("brightness", (0., 20., 0.1))],
+///         },
+///         put "/off",
+///     },
+/// };
+/// ```
+#[proc_macro]
+pub fn device_descriptor(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as device::Device);
+    device::expand_descriptor(&input).into()
+}
+
+// Returns the minimum capacity accepted by a fixed-capacity collection
+// to contain `len` elements.
+//
+// Collections accept only a power of two greater than one as capacity.
+pub(crate) fn capacity(len: usize) -> usize {
+    len.next_power_of_two().max(2)
+}
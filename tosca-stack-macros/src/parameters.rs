@@ -0,0 +1,257 @@
+use proc_macro2::{Span, TokenStream};
+
+use quote::quote;
+
+use syn::punctuated::Punctuated;
+use syn::{
+    Data, DeriveInput, Expr, ExprLit, ExprUnary, Field, Fields, Ident, Lit, LitFloat, LitStr,
+    Result, Token, Type, UnOp,
+};
+
+use crate::capacity;
+
+// Supported parameter types.
+#[derive(Clone, Copy, PartialEq)]
+enum ParameterType {
+    Bool,
+    U8,
+    U16,
+    U32,
+    U64,
+    F32,
+    F64,
+}
+
+impl ParameterType {
+    fn from_type(ty: &Type) -> Option<Self> {
+        let Type::Path(path) = ty else {
+            return None;
+        };
+        let ident = path.path.get_ident()?;
+        Some(match ident.to_string().as_str() {
+            "bool" => Self::Bool,
+            "u8" => Self::U8,
+            "u16" => Self::U16,
+            "u32" => Self::U32,
+            "u64" => Self::U64,
+            "f32" => Self::F32,
+            "f64" => Self::F64,
+            _ => return None,
+        })
+    }
+
+    const fn is_float(self) -> bool {
+        matches!(self, Self::F32 | Self::F64)
+    }
+
+    fn default_value(self) -> TokenStream {
+        match self {
+            Self::Bool => quote!(false),
+            Self::F32 | Self::F64 => quote!(0.),
+            _ => quote!(0),
+        }
+    }
+
+    fn builder(self) -> Ident {
+        let name = match self {
+            Self::Bool => "bool",
+            Self::U8 => "u8",
+            Self::U16 => "u16",
+            Self::U32 => "u32",
+            Self::U64 => "u64",
+            Self::F32 => "f32",
+            Self::F64 => "f64",
+        };
+        Ident::new(name, Span::call_site())
+    }
+}
+
+// A parameter described by a structure field.
+struct Parameter<'a> {
+    ident: &'a Ident,
+    name: LitStr,
+    ty: ParameterType,
+    default: Option<Expr>,
+    range: Option<[Expr; 3]>,
+}
+
+impl<'a> Parameter<'a> {
+    fn new(field: &'a Field) -> Result<Self> {
+        let ident = field
+            .ident
+            .as_ref()
+            .ok_or_else(|| syn::Error::new_spanned(field, "expected a named field"))?;
+
+        let ty = ParameterType::from_type(&field.ty).ok_or_else(|| {
+            syn::Error::new_spanned(
+                &field.ty,
+                "unsupported parameter type, expected one of: \
+                 `bool`, `u8`, `u16`, `u32`, `u64`, `f32`, `f64`",
+            )
+        })?;
+
+        let mut parameter = Self {
+            ident,
+            name: LitStr::new(&ident.to_string(), ident.span()),
+            ty,
+            default: None,
+            range: None,
+        };
+
+        for attr in field
+            .attrs
+            .iter()
+            .filter(|attr| attr.path().is_ident("tosca"))
+        {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("rename") {
+                    parameter.name = meta.value()?.parse()?;
+                } else if meta.path.is_ident("default") {
+                    parameter.default = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("range") {
+                    if !matches!(ty, ParameterType::U64 | ParameterType::F64) {
+                        return Err(meta.error("a range is supported only by `u64` and `f64`"));
+                    }
+                    let content;
+                    syn::parenthesized!(content in meta.input);
+                    let values = Punctuated::<Expr, Token![,]>::parse_terminated(&content)?;
+                    let values: [Expr; 3] = values
+                        .into_iter()
+                        .collect::<Vec<_>>()
+                        .try_into()
+                        .map_err(|_| meta.error("expected `range(min, max, step)`"))?;
+                    parameter.range = Some(values);
+                } else if meta.path.is_ident("unit") {
+                    return Err(meta.error(
+                        "units are not supported by the route parameters description format",
+                    ));
+                } else {
+                    return Err(meta.error("unsupported `tosca` attribute"));
+                }
+                Ok(())
+            })?;
+        }
+
+        Ok(parameter)
+    }
+
+    fn builder(&self) -> TokenStream {
+        let name = &self.name;
+        let default = self
+            .default
+            .as_ref()
+            .map_or_else(|| self.ty.default_value(), |value| self.literal(value));
+
+        if let Some([min, max, step]) = &self.range {
+            let (min, max, step) = (self.literal(min), self.literal(max), self.literal(step));
+            let builder = if self.ty.is_float() {
+                quote!(rangef64_with_default)
+            } else {
+                quote!(rangeu64_with_default)
+            };
+            quote!(.#builder(#name, (#min, #max, #step), #default))
+        } else {
+            let builder = self.ty.builder();
+            quote!(.#builder(#name, #default))
+        }
+    }
+
+    // Turns integer literals into float literals for float parameters.
+    fn literal(&self, value: &Expr) -> TokenStream {
+        if !self.ty.is_float() {
+            return quote!(#value);
+        }
+        match value {
+            Expr::Lit(ExprLit {
+                lit: Lit::Int(int), ..
+            }) => {
+                let float = LitFloat::new(&format!("{}.", int.base10_digits()), int.span());
+                quote!(#float)
+            }
+            Expr::Unary(ExprUnary {
+                op: UnOp::Neg(_),
+                expr,
+                ..
+            }) => {
+                let value = self.literal(expr);
+                quote!(-#value)
+            }
+            _ => quote!(#value),
+        }
+    }
+}
+
+pub(crate) fn expand(input: &DeriveInput) -> Result<TokenStream> {
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new_spanned(
+            input,
+            "`ToscaParameters` can be derived only for structures",
+        ));
+    };
+    let Fields::Named(fields) = &data.fields else {
+        return Err(syn::Error::new_spanned(
+            input,
+            "`ToscaParameters` can be derived only for structures with named fields",
+        ));
+    };
+
+    let parameters = fields
+        .named
+        .iter()
+        .map(Parameter::new)
+        .collect::<Result<Vec<_>>>()?;
+
+    for (index, parameter) in parameters.iter().enumerate() {
+        if parameters[..index]
+            .iter()
+            .any(|other| other.name.value() == parameter.name.value())
+        {
+            return Err(syn::Error::new(
+                parameter.name.span(),
+                "duplicate parameter name",
+            ));
+        }
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let n = capacity(parameters.len());
+
+    let builders = parameters.iter().map(Parameter::builder);
+    let idents = parameters
+        .iter()
+        .map(|parameter| parameter.ident)
+        .collect::<Vec<_>>();
+    let names = parameters
+        .iter()
+        .map(|parameter| &parameter.name)
+        .collect::<Vec<_>>();
+
+    Ok(quote! {
+        impl #impl_generics ::tosca_stack::parameters::ToscaParameters<#n>
+            for #ident #ty_generics #where_clause
+        {
+            fn parameters() -> ::tosca_stack::parameters::Parameters<#n> {
+                ::tosca_stack::parameters::Parameters::<#n>::new() #(#builders)*
+            }
+
+            fn parse<'a>(
+                values: impl ::core::iter::IntoIterator<Item = (&'a str, &'a str)>,
+            ) -> ::tosca_stack::error::Result<Self> {
+                let parameters =
+                    <Self as ::tosca_stack::parameters::ToscaParameters<#n>>::parameters();
+                #(let mut #idents = parameters.default_value(#names)?;)*
+                for (name, value) in values {
+                    let value = parameters.parse(name, value)?;
+                    match name {
+                        #(#names => #idents = value,)*
+                        _ => {}
+                    }
+                }
+                ::core::result::Result::Ok(Self {
+                    #(#idents: ::core::convert::TryFrom::try_from(#idents)?,)*
+                })
+            }
+        }
+    })
+}